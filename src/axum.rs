@@ -1,12 +1,202 @@
-use std::convert::Infallible;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+use std::{convert::Infallible, vec::Vec};
 
 use axum_core::{
     extract::FromRequestParts,
     response::{IntoResponse, IntoResponseParts, Response, ResponseParts},
 };
-use http::request::Parts;
+use http::{Request, StatusCode, header::SET_COOKIE, request::Parts};
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{
+    Cookie, CookieCodecs, CookieJar, CookiePolicy, ParseOptions, PercentCodec, SetCookieError,
+};
+
+/// Decides what happens when a [`CookieJar`] or [`Cookie`] returned from a handler can't be
+/// written to a `Set-Cookie` header, for example because of an invalid `Path` attribute.
+///
+/// Apply it to a router with [`SetCookieErrorLayer`]. Without the layer, the cookies that failed
+/// are dropped.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum SetCookieErrorPolicy {
+    /// Silently drop the cookies that failed, the other cookies are still set.
+    #[default]
+    Drop,
+    /// Call the hook for every cookie that failed, e.g. to log the error. The other cookies are
+    /// still set.
+    Hook(fn(&SetCookieError)),
+    /// Reject the response with a `500 Internal Server Error`.
+    Reject,
+}
+
+/// A [`Layer`] that applies a [`SetCookieErrorPolicy`] to the responses of the wrapped service.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use cookie_monster::{CookieJar, SetCookieError, SetCookieErrorLayer, SetCookieErrorPolicy};
+///
+/// fn log_error(error: &SetCookieError) {
+///     eprintln!("{error}");
+/// }
+///
+/// async fn handler(jar: CookieJar) -> CookieJar {
+///     jar
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/", get(handler))
+///     .layer(SetCookieErrorLayer::new(SetCookieErrorPolicy::Hook(log_error)));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SetCookieErrorLayer {
+    policy: SetCookieErrorPolicy,
+}
+
+impl SetCookieErrorLayer {
+    /// Creates a layer that applies the given policy.
+    pub const fn new(policy: SetCookieErrorPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for SetCookieErrorLayer {
+    type Service = SetCookieErrorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SetCookieErrorService {
+            inner,
+            policy: self.policy,
+        }
+    }
+}
 
-use crate::{Cookie, CookieJar};
+/// The service created by [`SetCookieErrorLayer`].
+#[derive(Debug, Clone)]
+pub struct SetCookieErrorService<S> {
+    inner: S,
+    policy: SetCookieErrorPolicy,
+}
+
+impl<S, B> Service<Request<B>> for SetCookieErrorService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = SetCookieErrorFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        SetCookieErrorFuture {
+            future: self.inner.call(request),
+            policy: self.policy,
+        }
+    }
+}
+
+pin_project! {
+    /// The response future of [`SetCookieErrorService`].
+    #[derive(Debug)]
+    pub struct SetCookieErrorFuture<F> {
+        #[pin]
+        future: F,
+        policy: SetCookieErrorPolicy,
+    }
+}
+
+impl<F, E> Future for SetCookieErrorFuture<F>
+where
+    F: Future<Output = Result<Response, E>>,
+{
+    type Output = Result<Response, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut response = ready!(this.future.poll(cx))?;
+
+        let Some(FailedCookies(errors)) = response.extensions_mut().remove() else {
+            return Poll::Ready(Ok(response));
+        };
+
+        match *this.policy {
+            SetCookieErrorPolicy::Drop => {}
+            SetCookieErrorPolicy::Hook(hook) => errors.iter().for_each(hook),
+            SetCookieErrorPolicy::Reject => {
+                response = SetCookieRejection { errors }.into_response();
+            }
+        }
+
+        Poll::Ready(Ok(response))
+    }
+}
+
+// The cookies of a response that could not be written, stored as a response extension until
+// `SetCookieErrorLayer` applies its policy.
+#[derive(Debug, Clone)]
+struct FailedCookies(Vec<SetCookieError>);
+
+fn record_errors(res: &mut ResponseParts, errors: Vec<SetCookieError>) {
+    match res.extensions_mut().get_mut::<FailedCookies>() {
+        Some(FailedCookies(failed)) => failed.extend(errors),
+        None => {
+            res.extensions_mut().insert(FailedCookies(errors));
+        }
+    }
+}
+
+/// Enables dev mode for the extracted [`CookieJar`] when added as a request extension, see
+/// [`CookieJar::set_dev_mode`].
+///
+/// Dev mode is enabled for every request, the `Host` header is not inspected since the client or
+/// a reverse proxy controls it. Only add this extension when the application runs in
+/// development, e.g. based on its configuration.
+///
+/// # Example
+/// ```rust
+/// use axum::{Extension, Router, routing::get};
+/// use cookie_monster::{CookieJar, DevMode};
+///
+/// async fn handler(jar: CookieJar) -> CookieJar {
+///     jar
+/// }
+///
+/// let mut app: Router = Router::new().route("/", get(handler));
+/// if cfg!(debug_assertions) {
+///     app = app.layer(Extension(DevMode));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DevMode;
+
+/// Replaces the response when cookies could not be written to it and the
+/// [`SetCookieErrorPolicy::Reject`] policy is used. Responds with a `500 Internal Server Error`.
+#[derive(Debug)]
+pub struct SetCookieRejection {
+    errors: Vec<SetCookieError>,
+}
+
+impl SetCookieRejection {
+    /// Returns the cookies that failed to be written.
+    pub fn errors(&self) -> &[SetCookieError] {
+        &self.errors
+    }
+}
+
+impl IntoResponse for SetCookieRejection {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to set cookies").into_response()
+    }
+}
 
 impl<S> FromRequestParts<S> for CookieJar
 where
@@ -15,15 +205,35 @@ where
     type Rejection = Infallible;
 
     async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
-        Ok(CookieJar::from_headers(&parts.headers))
+        let options = parts
+            .extensions
+            .get::<ParseOptions>()
+            .copied()
+            .unwrap_or_default();
+        let codecs = parts.extensions.get::<CookieCodecs>().cloned();
+
+        let mut jar = CookieJar::from_headers_inner(&parts.headers, &options, codecs);
+        if let Some(policy) = parts.extensions.get::<CookiePolicy>() {
+            jar.set_policy(policy.clone());
+        }
+
+        if parts.extensions.get::<DevMode>().is_some() {
+            jar.set_dev_mode(true);
+        }
+
+        Ok(jar)
     }
 }
 
+/// Appends all changes as `Set-Cookie` headers. The cookies that can't be written are handled by
+/// the [`SetCookieErrorLayer`], if any.
 impl IntoResponseParts for CookieJar {
     type Error = Infallible;
 
     fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
-        self.write_cookies(res.headers_mut());
+        if let Err(errors) = self.try_write_cookies(res.headers_mut()) {
+            record_errors(&mut res, errors);
+        }
         Ok(res)
     }
 }
@@ -34,16 +244,19 @@ impl IntoResponse for CookieJar {
     }
 }
 
+/// Appends the cookie as a `Set-Cookie` header. If the cookie can't be written, it's handled by
+/// the [`SetCookieErrorLayer`], if any.
 impl IntoResponseParts for Cookie {
     type Error = Infallible;
 
     fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
-        if let Some(cookie) = self
-            .serialize_encoded()
-            .ok()
-            .and_then(|string| string.parse().ok())
-        {
-            res.headers_mut().append("set-cookie", cookie);
+        match self.to_header_value(&PercentCodec) {
+            Ok(cookie) => {
+                res.headers_mut().append(SET_COOKIE, cookie);
+            }
+            Err(error) => {
+                record_errors(&mut res, std::vec![SetCookieError::new(self.name(), error)]);
+            }
         }
         Ok(res)
     }
@@ -0,0 +1,93 @@
+//! A minimal, unpadded base64url (RFC 4648 §5) implementation. Every character of the alphabet
+//! is a valid cookie-octet, so encoded values never need any further escaping.
+
+use alloc::{string::String, vec::Vec};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+pub(crate) fn encode(input: &[u8]) -> String {
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or_default() as u32;
+        let b2 = chunk.get(2).copied().unwrap_or_default() as u32;
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        // A chunk of n bytes produces n + 1 output characters, no padding is written.
+        for i in 0..=chunk.len() {
+            let index = (triple >> (18 - 6 * i)) & 0x3F;
+            out.push(ALPHABET[index as usize] as char);
+        }
+    }
+
+    out
+}
+
+pub(crate) fn decode(input: &str) -> Option<Vec<u8>> {
+    // A single trailing character can't encode a full byte.
+    if input.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(input.len() / 4 * 3 + 2);
+
+    for chunk in input.as_bytes().chunks(4) {
+        let mut triple = 0u32;
+
+        for (i, byte) in chunk.iter().enumerate() {
+            triple |= (decode_char(*byte)? as u32) << (18 - 6 * i);
+        }
+
+        for i in 0..(chunk.len() - 1) {
+            out.push((triple >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    Some(out)
+}
+
+fn decode_char(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'-' => Some(62),
+        b'_' => Some(63),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod base64_tests {
+    use super::{decode, encode};
+
+    #[test]
+    fn round_trip() {
+        for input in [
+            &b""[..],
+            b"f",
+            b"fo",
+            b"foo",
+            b"foob",
+            b"fooba",
+            b"foobar",
+            b"\xff\xfe",
+        ] {
+            assert_eq!(decode(&encode(input)).as_deref(), Some(input));
+        }
+    }
+
+    #[test]
+    fn rfc_vectors() {
+        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode(b"fooba"), "Zm9vYmE");
+        assert_eq!(encode(b"\xfb\xff"), "-_8");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(decode("Zm9vY"), None);
+        assert_eq!(decode("Zm9v+mFy"), None);
+    }
+}
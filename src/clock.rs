@@ -0,0 +1,69 @@
+use core::fmt::Debug;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A source of the current time.
+///
+/// The clock is used to create removal cookies, which expire one year before
+/// [`now`](Clock::now), see [`Expires::remove_with`](crate::Expires::remove_with) and
+/// [`CookieJar::set_clock`](crate::CookieJar::set_clock). Pass `clock.now()` to
+/// [`Cookie::is_expired`](crate::Cookie::is_expired) and friends for expiry checks.
+///
+/// Use [`FixedClock`] in tests to get deterministic `Set-Cookie` headers.
+///
+/// Only the `_with` variants and the jar use the clock: [`Cookie::remove`](crate::Cookie::remove),
+/// [`CookieTemplate::remove`](crate::CookieTemplate::remove) and
+/// [`Expires::remove`](crate::Expires::remove) always read the system clock, use
+/// [`Cookie::remove_with`](crate::Cookie::remove_with),
+/// [`CookieTemplate::remove_with`](crate::CookieTemplate::remove_with) and
+/// [`Expires::remove_with`](crate::Expires::remove_with) instead.
+pub trait Clock: Debug + Send + Sync + 'static {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// The system clock, returns [`SystemTime::now`]. This is the default clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that always returns the same time.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{Cookie, CookieJar, FixedClock};
+///
+/// let mut jar = CookieJar::from_cookie("session=abc");
+/// jar.set_clock(FixedClock::from_unix_timestamp(784_111_777));
+/// jar.remove(Cookie::named("session"));
+///
+/// let mut headers = jar.set_cookie_headers();
+/// assert_eq!(
+///     headers.next().unwrap().as_deref(),
+///     Ok("session=; Max-Age=0; Expires=Sat, 06 Nov 1993 08:49:37 GMT")
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(SystemTime);
+
+impl FixedClock {
+    /// Creates a clock that always returns `now`.
+    pub fn new(now: SystemTime) -> Self {
+        Self(now)
+    }
+
+    /// Creates a clock that always returns the given number of seconds since the Unix epoch.
+    pub fn from_unix_timestamp(secs: u64) -> Self {
+        Self(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
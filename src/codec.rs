@@ -0,0 +1,199 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::fmt::Debug;
+
+use crate::{Error, ErrorKind, base64};
+
+/// Decides how cookie names and values are encoded in `Set-Cookie` headers and decoded from the
+/// `Cookie` header.
+///
+/// The crate comes with [`RawCodec`], `PercentCodec` and [`Base64UrlCodec`]. Use
+/// [`CookieCodecs`] to select a codec for a [`CookieJar`](crate::CookieJar), with overrides for
+/// specific cookie names.
+///
+/// Encoded names and values are still validated when serializing, so a codec must only produce
+/// valid cookie characters.
+pub trait CookieCodec: Debug + Send + Sync + 'static {
+    /// Encodes a cookie name. Returns the name as is by default.
+    fn encode_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(name)
+    }
+
+    /// Encodes a cookie value.
+    fn encode_value<'a>(&self, value: &'a str) -> Cow<'a, str>;
+
+    /// Decodes a cookie name. Returns the name as is by default.
+    fn decode_name<'a>(&self, name: &'a str) -> crate::Result<Cow<'a, str>> {
+        Ok(Cow::Borrowed(name))
+    }
+
+    /// Decodes a cookie value.
+    fn decode_value<'a>(&self, value: &'a str) -> crate::Result<Cow<'a, str>>;
+}
+
+/// Leaves cookie names and values untouched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RawCodec;
+
+impl CookieCodec for RawCodec {
+    fn encode_value<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(value)
+    }
+
+    fn decode_value<'a>(&self, value: &'a str) -> crate::Result<Cow<'a, str>> {
+        Ok(Cow::Borrowed(value))
+    }
+}
+
+/// Percent-encodes cookie names and values, this is what [`Cookie::serialize_encoded`] and
+/// [`Cookie::parse_cookie_encoded`] use.
+///
+/// [`Cookie::serialize_encoded`]: crate::Cookie::serialize_encoded
+/// [`Cookie::parse_cookie_encoded`]: crate::Cookie::parse_cookie_encoded
+#[cfg(feature = "percent-encode")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PercentCodec;
+
+#[cfg(feature = "percent-encode")]
+impl CookieCodec for PercentCodec {
+    fn encode_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        crate::cookie::encoding::encode_name(name).into()
+    }
+
+    fn encode_value<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        crate::cookie::encoding::encode_value(value).into()
+    }
+
+    fn decode_name<'a>(&self, name: &'a str) -> crate::Result<Cow<'a, str>> {
+        crate::cookie::encoding::decode(name)
+    }
+
+    fn decode_value<'a>(&self, value: &'a str) -> crate::Result<Cow<'a, str>> {
+        crate::cookie::encoding::decode(value)
+    }
+}
+
+/// Encodes cookie values as unpadded base64url, names are left untouched.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{Base64UrlCodec, Cookie};
+///
+/// let cookie = Cookie::new("greeting", "hello, world");
+/// let header = cookie.serialize_with(&Base64UrlCodec).unwrap();
+/// assert_eq!(header, "greeting=aGVsbG8sIHdvcmxk");
+///
+/// let parsed = Cookie::parse_cookie_with(header, &Base64UrlCodec).unwrap();
+/// assert_eq!(parsed.value(), "hello, world");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Base64UrlCodec;
+
+impl CookieCodec for Base64UrlCodec {
+    fn encode_value<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        Cow::Owned(base64::encode(value.as_bytes()))
+    }
+
+    fn decode_value<'a>(&self, value: &'a str) -> crate::Result<Cow<'a, str>> {
+        base64::decode(value)
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .map(Cow::Owned)
+            .ok_or(Error::decode(ErrorKind::Base64DecodeError))
+    }
+}
+
+// Decodes the name and value of a cookie, or borrows them when no decoding is needed.
+pub(crate) fn decode_name_value<'a>(
+    codec: &dyn CookieCodec,
+    name: &'a str,
+    value: &'a str,
+) -> crate::Result<(Cow<'a, str>, Cow<'a, str>)> {
+    Ok((codec.decode_name(name)?, codec.decode_value(value)?))
+}
+
+/// Selects the [`CookieCodec`] for every cookie in a [`CookieJar`](crate::CookieJar): a default
+/// codec, with overrides for specific cookie names.
+///
+/// Overrides are matched against the cookie name without the `__Host-` / `__Secure-` prefix.
+///
+/// With the `axum` feature, add `CookieCodecs` as a request extension to configure the codecs of
+/// the extracted `CookieJar`.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{Base64UrlCodec, Cookie, CookieCodecs, CookieJar, RawCodec};
+///
+/// let codecs = CookieCodecs::new(RawCodec).with("data", Base64UrlCodec);
+///
+/// let jar = CookieJar::from_cookie_with("legacy=a%zz; data=aGVsbG8", codecs);
+/// assert_eq!(jar.get("legacy").map(Cookie::value), Some("a%zz"));
+/// assert_eq!(jar.get("data").map(Cookie::value), Some("hello"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CookieCodecs {
+    default: Arc<dyn CookieCodec>,
+    overrides: Vec<(Box<str>, Arc<dyn CookieCodec>)>,
+}
+
+impl CookieCodecs {
+    /// Creates a `CookieCodecs` that uses `codec` for all cookies.
+    pub fn new(codec: impl CookieCodec) -> Self {
+        Self {
+            default: Arc::new(codec),
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Creates a `CookieCodecs` that leaves all cookies untouched.
+    ///
+    /// With the `axum` feature, add this as a request extension to extract a `CookieJar` without
+    /// decoding the cookies. The changes in that jar are not encoded either.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "axum")]
+    /// # {
+    /// use axum::{Extension, Router, routing::get};
+    /// use cookie_monster::{CookieCodecs, CookieJar};
+    ///
+    /// async fn handler(jar: CookieJar) -> CookieJar {
+    ///     jar
+    /// }
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/", get(handler))
+    ///     .layer(Extension(CookieCodecs::raw()));
+    /// # }
+    /// ```
+    pub fn raw() -> Self {
+        Self::new(RawCodec)
+    }
+
+    /// Uses `codec` for the cookies with the given name.
+    pub fn with(mut self, name: impl Into<Box<str>>, codec: impl CookieCodec) -> Self {
+        let name = name.into();
+        self.overrides.retain(|(n, _)| *n != name);
+        self.overrides.push((name, Arc::new(codec)));
+        self
+    }
+
+    /// Returns the codec for the cookies with the given name.
+    pub fn get(&self, name: &str) -> &dyn CookieCodec {
+        self.overrides
+            .iter()
+            .find(|(n, _)| **n == *name)
+            .map(|(_, codec)| codec)
+            .unwrap_or(&self.default)
+            .as_ref()
+    }
+}
+
+impl Default for CookieCodecs {
+    /// Percent-encodes all cookies if the `percent-encode` feature is enabled, otherwise all
+    /// cookies are left untouched.
+    fn default() -> Self {
+        #[cfg(feature = "percent-encode")]
+        return Self::new(PercentCodec);
+
+        #[cfg(not(feature = "percent-encode"))]
+        return Self::new(RawCodec);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    borrow::Cow,
+    io::{Read, Write},
+    string::String,
+    vec::Vec,
+};
+
+use flate2::{read::DeflateDecoder, write::DeflateEncoder};
+
+use crate::{Error, ErrorKind, base64};
+
+/// An opt-in codec that deflate-compresses cookie values.
+///
+/// Compressed values are base64url-encoded and prefixed with [`Compression::MARKER`], so they only
+/// contain valid cookie characters and can be detected when they're sent back by the user-agent.
+///
+/// Attach it to a [`CookieJar`](crate::CookieJar) with
+/// [`CookieJar::set_compression`](crate::CookieJar::set_compression) to compress newly added
+/// cookies and transparently inflate compressed cookies from the `Cookie` header.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::Compression;
+///
+/// let compression = Compression::new();
+/// let value = "a".repeat(100);
+///
+/// let compressed = compression.compress(&value);
+/// assert!(compressed.starts_with(Compression::MARKER));
+/// assert!(compressed.len() < value.len());
+///
+/// assert_eq!(compression.decompress(&compressed).as_deref(), Ok(value.as_str()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Compression {
+    level: u32,
+    max_decompressed_size: usize,
+}
+
+impl Compression {
+    /// The prefix that marks a compressed cookie value.
+    pub const MARKER: &'static str = "~z";
+
+    /// Creates a new `Compression` with the default compression level and a decompression bound of
+    /// 16 KiB.
+    pub const fn new() -> Self {
+        Self {
+            level: 6,
+            max_decompressed_size: 16 * 1024,
+        }
+    }
+
+    /// Sets the compression level, ranging from 0 (no compression) to 9 (best compression).
+    pub const fn level(mut self, level: u32) -> Self {
+        self.level = if level > 9 { 9 } else { level };
+        self
+    }
+
+    /// Sets the maximum size in bytes a value is allowed to decompress to. Values that inflate
+    /// past this bound are rejected with [`ErrorKind::DecompressedTooLarge`], this protects against
+    /// zip-bomb style values sent in the `Cookie` header.
+    pub const fn max_decompressed_size(mut self, max: usize) -> Self {
+        self.max_decompressed_size = max;
+        self
+    }
+
+    /// Returns if the value is marked as compressed.
+    pub fn is_compressed(value: &str) -> bool {
+        value.starts_with(Self::MARKER)
+    }
+
+    /// Compresses the value. The value is returned as is if compressing doesn't make it any
+    /// smaller, unless it starts with [`Compression::MARKER`]. These values are always compressed,
+    /// so they can't be mistaken for compressed values.
+    pub fn compress<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::new(self.level));
+
+        // Writing to a `Vec` never fails.
+        let compressed = encoder
+            .write_all(value.as_bytes())
+            .and_then(|_| encoder.finish())
+            .expect("Failed to compress the cookie value");
+
+        let mut encoded = String::from(Self::MARKER);
+        encoded.push_str(&base64::encode(&compressed));
+
+        if encoded.len() < value.len() || Self::is_compressed(value) {
+            Cow::Owned(encoded)
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+
+    /// Decompresses the value if it's marked as compressed, values without the marker are returned
+    /// as is. Errors when:
+    /// * The value is not valid base64url or deflate data.
+    /// * The value decompresses to invalid UTF-8.
+    /// * The value decompresses to more than the configured maximum size.
+    pub fn decompress<'a>(&self, value: &'a str) -> crate::Result<Cow<'a, str>> {
+        let Some(encoded) = value.strip_prefix(Self::MARKER) else {
+            return Ok(Cow::Borrowed(value));
+        };
+
+        let compressed =
+            base64::decode(encoded).ok_or(Error::decode(ErrorKind::DecompressError))?;
+
+        // Read one byte past the bound, so we can tell if the value is too large.
+        let mut decompressed = Vec::new();
+        DeflateDecoder::new(compressed.as_slice())
+            .take(self.max_decompressed_size as u64 + 1)
+            .read_to_end(&mut decompressed)
+            .map_err(|_| Error::decode(ErrorKind::DecompressError))?;
+
+        if decompressed.len() > self.max_decompressed_size {
+            return Err(Error::decode(ErrorKind::DecompressedTooLarge));
+        }
+
+        String::from_utf8(decompressed)
+            .map(Cow::Owned)
+            .map_err(|_| Error::decode(ErrorKind::DecompressError))
+    }
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::borrow::Cow;
+
+use super::{
+    Cookie,
+    parse::{offset_of, split_name_value},
+    prefix::{CookiePrefix, split_prefix},
+};
+use crate::{ParseProfile, util::TinyStr};
+
+/// A cookie that borrows its name and value from the `Cookie` header it was parsed from.
+///
+/// Returned by [`Cookie::split_parse`]. Use [`CookieRef::into_owned`] to turn it into a
+/// [`Cookie`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieRef<'a> {
+    name: Cow<'a, str>,
+    value: Cow<'a, str>,
+    prefix: Option<CookiePrefix>,
+    quoted: bool,
+}
+
+impl<'a> CookieRef<'a> {
+    fn parse(
+        name_value: &'a str,
+        callback: impl Fn(&'a str, &'a str) -> crate::Result<(Cow<'a, str>, Cow<'a, str>)>,
+    ) -> crate::Result<CookieRef<'a>> {
+        let (name, value, quoted) = split_name_value(name_value, ParseProfile::Lenient)?;
+        let (name, value) = callback(name, value).map_err(|e| e.with_name(name))?;
+
+        // Strip a recognized `__Host-` / `__Secure-` prefix off the name and remember it.
+        let (prefix, name) = split_prefix(name);
+
+        Ok(CookieRef {
+            name,
+            value,
+            prefix,
+            quoted,
+        })
+    }
+
+    /// Returns the cookie name, without a `__Host-` / `__Secure-` prefix.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the cookie value, without the surrounding double quotes.
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns if the value was surrounded by double quotes.
+    #[inline]
+    pub fn is_quoted(&self) -> bool {
+        self.quoted
+    }
+
+    /// Converts this into an owned [`Cookie`].
+    pub fn into_owned(self) -> Cookie {
+        let name = TinyStr::from(self.name.into_owned());
+        let value = TinyStr::from(self.value.into_owned());
+
+        let mut cookie = Cookie::new_inner(name, value);
+        cookie.prefix = self.prefix;
+        cookie.parsed_quoted = self.quoted;
+        cookie
+    }
+}
+
+impl From<CookieRef<'_>> for Cookie {
+    fn from(value: CookieRef<'_>) -> Self {
+        value.into_owned()
+    }
+}
+
+impl Cookie {
+    /// Parses every cookie in the given `Cookie` header value, without copying the names and
+    /// values. Empty entries, for example a trailing `;`, are skipped.
+    ///
+    /// Each item fails for the same reasons as [`Cookie::parse_cookie`], a cookie that fails to
+    /// parse doesn't affect the other cookies.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::Cookie;
+    ///
+    /// let mut cookies = Cookie::split_parse("foo=bar; baz=\"qux\"");
+    ///
+    /// let foo = cookies.next().unwrap().unwrap();
+    /// assert_eq!((foo.name(), foo.value()), ("foo", "bar"));
+    ///
+    /// let baz = cookies.next().unwrap().unwrap();
+    /// assert_eq!((baz.name(), baz.value()), ("baz", "qux"));
+    ///
+    /// assert!(cookies.next().is_none());
+    /// ```
+    pub fn split_parse(header: &str) -> impl Iterator<Item = crate::Result<CookieRef<'_>>> {
+        split_header(header).map(|name_value| {
+            CookieRef::parse(name_value, |name, value| {
+                Ok((Cow::Borrowed(name), Cow::Borrowed(value)))
+            })
+            .map_err(|e| e.shift_offset(offset_of(header, name_value)))
+        })
+    }
+
+    /// Parses and percent-decodes every cookie in the given `Cookie` header value. Names and
+    /// values are only copied if decoding changes them. Empty entries, for example a trailing
+    /// `;`, are skipped.
+    ///
+    /// Each item fails for the same reasons as [`Cookie::parse_cookie_encoded`], a cookie that
+    /// fails to parse doesn't affect the other cookies.
+    #[cfg(feature = "percent-encode")]
+    pub fn split_parse_encoded(header: &str) -> impl Iterator<Item = crate::Result<CookieRef<'_>>> {
+        use crate::{PercentCodec, codec::decode_name_value};
+
+        split_header(header).map(|name_value| {
+            CookieRef::parse(name_value, |name, value| {
+                decode_name_value(&PercentCodec, name, value)
+            })
+            .map_err(|e| e.shift_offset(offset_of(header, name_value)))
+        })
+    }
+}
+
+fn split_header(header: &str) -> impl Iterator<Item = &str> {
+    header
+        .split(';')
+        .filter(|name_value| !name_value.trim().is_empty())
+}
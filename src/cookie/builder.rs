@@ -1,16 +1,24 @@
-use std::{
-    borrow::{Borrow, Cow},
-    fmt,
-    time::Duration,
-};
+use alloc::borrow::Cow;
+use core::{borrow::Borrow, fmt, time::Duration};
 
-use crate::Cookie;
+use crate::{Cookie, CookieCategory, CookieCodec, MaxAge, RawCodec};
+
+#[cfg(feature = "std")]
+use {crate::Clock, alloc::sync::Arc};
 
 use super::{expires::Expires, same_site::SameSite};
 
 /// A builder struct for building a [`Cookie`].
-#[derive(PartialEq, Clone)]
-pub struct CookieBuilder(Cookie);
+#[derive(Clone)]
+pub struct CookieBuilder {
+    cookie: Cookie,
+    // The clock used by `expires_in`, the system clock if not set.
+    #[cfg(feature = "std")]
+    clock: Option<Arc<dyn Clock>>,
+    // The duration passed to `expires_in`, so the expiry is recomputed when the clock changes.
+    #[cfg(feature = "std")]
+    expires_in: Option<MaxAge>,
+}
 
 impl CookieBuilder {
     /// Build a new cookie. This returns a `CookieBuilder` that can be used to set other attribute
@@ -34,44 +42,50 @@ impl CookieBuilder {
         N: Into<Cow<'static, str>>,
         V: Into<Cow<'static, str>>,
     {
-        CookieBuilder(Cookie::new(name, value))
+        CookieBuilder {
+            cookie: Cookie::new(name, value),
+            #[cfg(feature = "std")]
+            clock: None,
+            #[cfg(feature = "std")]
+            expires_in: None,
+        }
     }
 
     /// Sets the name of the cookie.
     #[inline]
     pub fn name<N: Into<Cow<'static, str>>>(mut self, name: N) -> Self {
-        self.0.set_name(name);
+        self.cookie.set_name(name);
         self
     }
 
     /// Returns the name of the cookie.
     pub fn get_name(&self) -> &str {
-        self.0.name()
+        self.cookie.name()
     }
 
     /// Sets the name of the cookie.
     #[inline]
     pub fn set_name<N: Into<Cow<'static, str>>>(&mut self, name: N) {
-        self.0.set_name(name);
+        self.cookie.set_name(name);
     }
 
     /// Stores the cookie name prefix flavour. Used by [`Cookie::host`] / [`Cookie::secure`].
     #[inline]
     pub(crate) fn with_prefix(mut self, prefix: super::prefix::CookiePrefix) -> Self {
-        self.0.prefix = Some(prefix);
+        self.cookie.prefix = Some(prefix);
         self
     }
 
     /// Sets the value of the cookie.
     #[inline]
     pub fn value<V: Into<Cow<'static, str>>>(mut self, value: V) -> Self {
-        self.0.set_value(value);
+        self.cookie.set_value(value);
         self
     }
 
     /// Returns the value of the cookie.
     pub fn get_value(&self) -> &str {
-        self.0.value()
+        self.cookie.value()
     }
 
     /// Sets the value of the cookie.
@@ -79,7 +93,26 @@ impl CookieBuilder {
     where
         V: Into<Cow<'static, str>>,
     {
-        self.0.value = value.into().into();
+        self.cookie.value = value.into().into();
+    }
+
+    /// Surrounds the value with double quotes when the cookie is serialized, see
+    /// [`Cookie::set_quoted`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::Cookie;
+    ///
+    /// let cookie = Cookie::build("foo", "bar")
+    ///     .quoted()
+    ///     .build();
+    ///
+    /// assert_eq!(cookie.serialize().as_deref(), Ok("foo=\"bar\""));
+    /// ```
+    #[inline]
+    pub fn quoted(mut self) -> Self {
+        self.cookie.set_quoted(true);
+        self
     }
 
     /// Sets the Expires attribute of the cookie.
@@ -98,6 +131,24 @@ impl CookieBuilder {
     /// assert!(cookie.expires_is_set());
     /// ```
     ///
+    /// # Std
+    /// ```rust
+    /// # #[cfg(feature="std")]
+    /// # {
+    /// # use cookie_monster::Cookie;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let cookie = Cookie::build("foo", "bar")
+    ///     .expires(UNIX_EPOCH + Duration::from_secs(784_111_777))
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     cookie.serialize().as_deref(),
+    ///     Ok("foo=bar; Expires=Sun, 06 Nov 1994 08:49:37 GMT")
+    /// );
+    /// # }
+    /// ```
+    ///
     /// # Jiff
     /// ```rust
     /// # #[cfg(feature="jiff")]
@@ -144,13 +195,68 @@ impl CookieBuilder {
     /// ```
     #[inline]
     pub fn expires(mut self, expiration: impl Into<Expires>) -> Self {
-        self.0.set_expires(expiration.into());
+        self.set_expires(expiration);
         self
     }
 
     /// Sets the Expires attribute of the cookie.
     pub fn set_expires(&mut self, expiration: impl Into<Expires>) {
-        self.0.set_expires(expiration.into());
+        self.cookie.set_expires(expiration.into());
+        #[cfg(feature = "std")]
+        {
+            self.expires_in = None;
+        }
+    }
+
+    /// Sets the clock that is used by [`expires_in`](Self::expires_in). Defaults to the
+    /// [`SystemClock`](crate::SystemClock).
+    ///
+    /// If `expires_in` was already called, the Expires attribute is recomputed with the new
+    /// clock.
+    #[cfg(feature = "std")]
+    pub fn clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Some(Arc::new(clock));
+        if let Some(duration) = self.expires_in {
+            self = self.expires_in(duration);
+        }
+        self
+    }
+
+    /// Sets the Expires attribute to the current time of the [clock](Self::clock) plus the given
+    /// duration. The duration can be any type that converts into a [`MaxAge`].
+    ///
+    /// The clock is read when this is called, and again if the clock is changed afterwards.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use cookie_monster::{Cookie, FixedClock};
+    ///
+    /// let cookie = Cookie::build("foo", "bar")
+    ///     .clock(FixedClock::from_unix_timestamp(784_111_777))
+    ///     .expires_in(Duration::from_secs(3600))
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     cookie.serialize().as_deref(),
+    ///     Ok("foo=bar; Expires=Sun, 06 Nov 1994 09:49:37 GMT")
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn expires_in(mut self, duration: impl Into<MaxAge>) -> Self {
+        let duration = duration.into();
+        let now = match &self.clock {
+            Some(clock) => clock.now(),
+            None => crate::SystemClock.now(),
+        };
+        let now = Expires::from(now).unix_timestamp().unwrap_or_default();
+
+        self.cookie.set_expires(Expires::from_unix_timestamp(
+            now.saturating_add(duration.as_secs()),
+        ));
+        self.expires_in = Some(duration);
+        self
     }
 
     /// Sets the Max-Age attribute of the cookie.
@@ -166,17 +272,19 @@ impl CookieBuilder {
     /// assert_eq!(cookie.max_age_secs(), Some(100));
     /// ```
     #[inline]
-    pub fn max_age_secs(mut self, max_age_secs: u64) -> Self {
-        self.0.set_max_age_secs(max_age_secs);
+    pub fn max_age_secs(mut self, max_age_secs: i64) -> Self {
+        self.cookie.set_max_age_secs(max_age_secs);
         self
     }
 
     /// Sets the Max-Age attribute in seconds.
-    pub fn set_max_age_secs(&mut self, max_age_secs: u64) {
-        self.0.set_max_age_secs(max_age_secs);
+    pub fn set_max_age_secs(&mut self, max_age_secs: i64) {
+        self.cookie.set_max_age_secs(max_age_secs);
     }
 
-    /// Sets the Max-Age attribute of the cookie.
+    /// Sets the Max-Age attribute of the cookie. The argument can be any type that converts into a
+    /// [`MaxAge`], such as a `std::time::Duration`, or with the datetime features a
+    /// `time::Duration`, `chrono::TimeDelta` or `jiff::SignedDuration`.
     ///
     /// # Example
     /// ```rust
@@ -190,20 +298,20 @@ impl CookieBuilder {
     /// assert_eq!(cookie.max_age(), Some(Duration::from_secs(100)));
     /// ```
     #[inline]
-    pub fn max_age(mut self, max_age: Duration) -> Self {
-        self.0.set_max_age(max_age);
+    pub fn max_age(mut self, max_age: impl Into<MaxAge>) -> Self {
+        self.cookie.set_max_age(max_age);
         self
     }
 
     /// Returns the Max-Age attribute of the cookie.
     pub fn get_max_age(&self) -> Option<Duration> {
-        self.0.max_age()
+        self.cookie.max_age()
     }
 
     /// Sets the Max-Age attribute of the cookie.
     #[inline]
-    pub fn set_max_age(&mut self, max_age: Duration) {
-        self.0.set_max_age(max_age);
+    pub fn set_max_age(&mut self, max_age: impl Into<MaxAge>) {
+        self.cookie.set_max_age(max_age);
     }
 
     /// Sets the Domain attribute of the cookie.
@@ -224,14 +332,14 @@ impl CookieBuilder {
     /// ```
     #[inline]
     pub fn domain<D: Into<Cow<'static, str>>>(mut self, domain: D) -> Self {
-        self.0.set_domain(domain);
+        self.cookie.set_domain(domain);
         self
     }
 
     /// Sets the Domain attribute of the cookie.
     #[inline]
     pub fn set_domain<D: Into<Cow<'static, str>>>(&mut self, domain: D) {
-        self.0.set_domain(domain);
+        self.cookie.set_domain(domain);
     }
 
     /// Sets the Path attribute of the cookie.
@@ -256,19 +364,19 @@ impl CookieBuilder {
     /// ```
     #[inline]
     pub fn path<D: Into<Cow<'static, str>>>(mut self, path: D) -> Self {
-        self.0.set_path(path);
+        self.cookie.set_path(path);
         self
     }
 
     /// Returns the Path attribute of the cookie.
     pub fn get_path(&self) -> Option<&str> {
-        self.0.path()
+        self.cookie.path()
     }
 
     /// Sets the Path attribute of the cookie.
     #[inline]
     pub fn set_path<D: Into<Cow<'static, str>>>(&mut self, path: D) {
-        self.0.set_path(path);
+        self.cookie.set_path(path);
     }
 
     /// Sets the Secure attribute of the cookie.
@@ -285,14 +393,14 @@ impl CookieBuilder {
     /// ```
     #[inline]
     pub fn secure(mut self) -> Self {
-        self.0.set_secure(true);
+        self.cookie.set_secure(true);
         self
     }
 
     /// Sets the Secure attribute.
     #[inline]
     pub fn set_secure(mut self, secure: bool) -> Self {
-        self.0.set_secure(secure);
+        self.cookie.set_secure(secure);
         self
     }
 
@@ -310,14 +418,14 @@ impl CookieBuilder {
     /// ```
     #[inline]
     pub fn http_only(mut self) -> Self {
-        self.0.set_http_only(true);
+        self.cookie.set_http_only(true);
         self
     }
 
     /// Sets the HttpOnly attribute of the cookie.
     #[inline]
     pub fn set_http_only(mut self, http_only: bool) -> Self {
-        self.0.set_http_only(http_only);
+        self.cookie.set_http_only(http_only);
         self
     }
 
@@ -344,7 +452,7 @@ impl CookieBuilder {
     /// Set the Partitioned flag, enabling the Partitioned attribute also enables the Secure Attribute.
     #[inline]
     pub fn set_partitioned(mut self, partitioned: bool) -> Self {
-        self.0.set_partitioned(partitioned);
+        self.cookie.set_partitioned(partitioned);
         self
     }
 
@@ -362,32 +470,102 @@ impl CookieBuilder {
     /// ```
     #[inline]
     pub fn same_site<S: Into<Option<SameSite>>>(mut self, same_site: S) -> Self {
-        self.0.set_same_site(same_site);
+        self.cookie.set_same_site(same_site);
+        self
+    }
+
+    /// Sets the consent category of the cookie, see [`CookieCategory`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieCategory};
+    ///
+    /// let cookie = Cookie::build("_ga", "GA1.1")
+    ///     .category(CookieCategory::Analytics)
+    ///     .build();
+    ///
+    /// assert_eq!(cookie.category(), CookieCategory::Analytics);
+    /// ```
+    #[inline]
+    pub fn category(mut self, category: CookieCategory) -> Self {
+        self.cookie.set_category(category);
         self
     }
 
     /// Builds and returns the cookie
     #[inline]
     pub fn build(self) -> Cookie {
-        self.0
+        self.cookie
+    }
+
+    /// Validates and returns the cookie, without serializing it. Errors when:
+    /// * The name is empty.
+    /// * Name or value contain an invalid cookie character, use
+    ///   [`try_build_with`](Self::try_build_with) if the cookie is encoded when it's written.
+    /// * Path attribute is empty, does not start with a leading '/' or contains an invalid cookie
+    ///   character.
+    /// * Domain attribute contains an invalid cookie character.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, ErrorKind};
+    ///
+    /// let cookie = Cookie::build("session", "abc").path("/").try_build();
+    /// assert!(cookie.is_ok());
+    ///
+    /// let error = Cookie::build("session", "abc").path("no-slash").try_build();
+    /// assert_eq!(error.unwrap_err().kind(), ErrorKind::NoLeadingSlash);
+    ///
+    /// let error = Cookie::build("session", "abc").domain("exa mple.com").try_build();
+    /// assert_eq!(error.unwrap_err().kind(), ErrorKind::InvalidDomainValue(' '));
+    /// ```
+    pub fn try_build(self) -> crate::Result<Cookie> {
+        self.try_build_with(&RawCodec)
+    }
+
+    /// Validates and returns the cookie. Errors for the same reasons as
+    /// [`try_build`](Self::try_build), but the name and value are validated after encoding them
+    /// with the given [`CookieCodec`], like [`Cookie::serialize_with`] does.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Base64UrlCodec, Cookie, ErrorKind};
+    ///
+    /// let error = Cookie::build("session", "hello, world").try_build();
+    /// assert_eq!(error.unwrap_err().kind(), ErrorKind::InvalidValue(','));
+    ///
+    /// let cookie = Cookie::build("session", "hello, world").try_build_with(&Base64UrlCodec);
+    /// assert!(cookie.is_ok());
+    /// ```
+    pub fn try_build_with(self, codec: &dyn CookieCodec) -> crate::Result<Cookie> {
+        self.cookie
+            .validate_with(codec)
+            .map_err(|e| e.with_name(self.cookie.name()))?;
+        Ok(self.cookie)
+    }
+}
+
+impl PartialEq for CookieBuilder {
+    fn eq(&self, other: &Self) -> bool {
+        self.cookie == other.cookie
     }
 }
 
 impl fmt::Debug for CookieBuilder {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        fmt::Debug::fmt(&self.0, f)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.cookie, f)
     }
 }
 
 impl fmt::Display for CookieBuilder {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.cookie, f)
     }
 }
 
 impl Borrow<Cookie> for CookieBuilder {
     fn borrow(&self) -> &Cookie {
-        &self.0
+        &self.cookie
     }
 }
 
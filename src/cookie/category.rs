@@ -0,0 +1,142 @@
+use core::fmt;
+
+/// The consent category of a cookie, used to filter the `Set-Cookie` headers of a
+/// [`CookieJar`](crate::CookieJar) by [`Consent`].
+///
+/// The category is not sent to the user-agent. Cookies are
+/// [`Necessary`](CookieCategory::Necessary) by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CookieCategory {
+    /// Strictly necessary cookies, these are always allowed.
+    #[default]
+    Necessary,
+    /// Cookies that remember choices of the user, like the language or theme.
+    Preferences,
+    /// Cookies that measure how the site is used, like visitor statistics.
+    Analytics,
+    /// Cookies that track the user for advertising.
+    Marketing,
+}
+
+impl CookieCategory {
+    /// Returns the name of the category as used in the consent cookie.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            CookieCategory::Necessary => "necessary",
+            CookieCategory::Preferences => "preferences",
+            CookieCategory::Analytics => "analytics",
+            CookieCategory::Marketing => "marketing",
+        }
+    }
+
+    fn from_str(category: &str) -> Option<Self> {
+        match category {
+            "necessary" => Some(CookieCategory::Necessary),
+            "preferences" => Some(CookieCategory::Preferences),
+            "analytics" => Some(CookieCategory::Analytics),
+            "marketing" => Some(CookieCategory::Marketing),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CookieCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The cookie categories a user consented to. [`Necessary`](CookieCategory::Necessary) cookies
+/// are always allowed.
+///
+/// In the consent cookie, the categories are separated by a `|`, e.g. `analytics|marketing`.
+/// Unknown categories are ignored.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{Consent, CookieCategory};
+///
+/// let consent = Consent::parse("preferences|analytics");
+/// assert!(consent.allows(CookieCategory::Necessary));
+/// assert!(consent.allows(CookieCategory::Analytics));
+/// assert!(!consent.allows(CookieCategory::Marketing));
+///
+/// let consent = Consent::none().with(CookieCategory::Marketing);
+/// assert_eq!(consent.to_string(), "marketing");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Consent {
+    preferences: bool,
+    analytics: bool,
+    marketing: bool,
+}
+
+impl Consent {
+    /// Consent for necessary cookies only.
+    pub const fn none() -> Self {
+        Self {
+            preferences: false,
+            analytics: false,
+            marketing: false,
+        }
+    }
+
+    /// Consent for all categories.
+    pub const fn all() -> Self {
+        Self {
+            preferences: true,
+            analytics: true,
+            marketing: true,
+        }
+    }
+
+    /// Adds consent for the given category.
+    pub const fn with(mut self, category: CookieCategory) -> Self {
+        match category {
+            CookieCategory::Necessary => {}
+            CookieCategory::Preferences => self.preferences = true,
+            CookieCategory::Analytics => self.analytics = true,
+            CookieCategory::Marketing => self.marketing = true,
+        }
+        self
+    }
+
+    /// Returns if cookies of the given category may be set.
+    pub const fn allows(&self, category: CookieCategory) -> bool {
+        match category {
+            CookieCategory::Necessary => true,
+            CookieCategory::Preferences => self.preferences,
+            CookieCategory::Analytics => self.analytics,
+            CookieCategory::Marketing => self.marketing,
+        }
+    }
+
+    /// Parses the value of a consent cookie.
+    pub fn parse(value: &str) -> Self {
+        value
+            .split('|')
+            .filter_map(|category| CookieCategory::from_str(category.trim()))
+            .fold(Self::none(), Self::with)
+    }
+}
+
+/// Formats the consent as the value of a consent cookie.
+impl fmt::Display for Consent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let categories = [
+            CookieCategory::Preferences,
+            CookieCategory::Analytics,
+            CookieCategory::Marketing,
+        ];
+
+        let mut first = true;
+        for category in categories.into_iter().filter(|c| self.allows(*c)) {
+            if !first {
+                f.write_str("|")?;
+            }
+            f.write_str(category.as_str())?;
+            first = false;
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-use crate::cookie::parse::find_invalid_cookie_value;
+use alloc::string::String;
+
+use crate::{Error, ErrorKind, cookie::parse::find_invalid_cookie_value, error::Attribute};
 
 use super::Cookie;
 
@@ -24,4 +26,22 @@ impl Cookie {
         buf.push_str("; Domain=");
         buf.push_str(domain);
     }
+
+    // Serializing ignores invalid domains, this is the strict check used by `try_build`.
+    pub(crate) fn validate_domain(&self) -> crate::Result<()> {
+        let Some(domain) = self.domain_sanitized() else {
+            return Ok(());
+        };
+
+        if let Some((i, invalid_char)) = find_invalid_cookie_value(domain) {
+            let offset = self.domain().map_or(0, |d| d.len() - domain.len());
+            return Err(Error::serialize(
+                ErrorKind::InvalidDomainValue(invalid_char),
+                Attribute::Domain,
+            )
+            .with_offset(offset + i));
+        }
+
+        Ok(())
+    }
 }
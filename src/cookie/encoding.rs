@@ -1,19 +1,12 @@
-use crate::Error;
-use percent_encoding::{AsciiSet, CONTROLS, percent_decode, percent_encode};
+use crate::{Error, ErrorKind};
+use percent_encoding::{AsciiSet, CONTROLS, PercentEncode, percent_decode, percent_encode};
 
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
-pub(crate) fn decode_name_value<'a>(
-    name: &'a str,
-    val: &'a str,
-) -> crate::Result<(Cow<'a, str>, Cow<'a, str>)> {
-    Ok((decode(name)?, decode(val)?))
-}
-
-fn decode(value: &str) -> crate::Result<Cow<'_, str>> {
+pub(crate) fn decode(value: &str) -> crate::Result<Cow<'_, str>> {
     percent_decode(value.as_bytes())
         .decode_utf8()
-        .map_err(|_| Error::PercentDecodeError)
+        .map_err(|_| Error::decode(ErrorKind::PercentDecodeError))
 }
 
 // %x21 / %x23-2B / %x2D-3A / %x3C-5B / %x5D-7E
@@ -40,10 +33,10 @@ const FORBIDDEN_NAME: &AsciiSet = &FORBIDDEN_VALUE
     .add(b'{')
     .add(b'}');
 
-pub fn encode_name(string: &str) -> impl std::fmt::Display + '_ {
+pub fn encode_name(string: &str) -> PercentEncode<'_> {
     percent_encode(string.as_bytes(), FORBIDDEN_NAME)
 }
 
-pub fn encode_value(string: &str) -> impl std::fmt::Display + '_ {
+pub fn encode_value(string: &str) -> PercentEncode<'_> {
     percent_encode(string.as_bytes(), FORBIDDEN_VALUE)
 }
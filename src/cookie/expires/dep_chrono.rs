@@ -1,40 +1,48 @@
-use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
-use crate::{Cookie, Error, cookie::expires::ExpVal};
+use crate::Cookie;
 
 use super::Expires;
 
-// Sun, 06 Nov 1994 08:49:37 GMT (RFC)
-static FMT: &str = "%a, %d %b %Y %T GMT";
-
-static MAX_EXPIRES: DateTime<Utc> = NaiveDateTime::new(
-    NaiveDate::from_ymd_opt(9999, 12, 31).unwrap(),
-    NaiveTime::from_hms_micro_opt(23, 59, 59, 59).unwrap(),
-)
-.and_utc();
-
 impl From<DateTime<Utc>> for Expires {
     fn from(value: DateTime<Utc>) -> Self {
-        Self::Exp(ExpVal {
-            chrono: Some(std::cmp::min(value, MAX_EXPIRES)),
-            ..Default::default()
-        })
+        Self::from_unix_timestamp(value.timestamp())
     }
 }
 
 impl Cookie {
     /// Returns the Expires attribute using [`chrono::DateTime`].
     pub fn expires_chrono(&self) -> Option<DateTime<Utc>> {
-        match &self.expires {
-            Expires::Exp(ExpVal { chrono, .. }) => *chrono,
-            _ => None,
-        }
+        self.expires.to_chrono()
     }
 
     /// Returns the Max-Age attribute using [`chrono::Duration`].
     pub fn max_age_chrono(&self) -> Option<Duration> {
-        self.max_age_secs()
-            .map(|max_age| Duration::seconds(max_age as i64))
+        self.max_age_secs().and_then(Duration::try_seconds)
+    }
+
+    /// Returns when the cookie expires using a [`chrono::DateTime`], see
+    /// [`Cookie::effective_expiry`].
+    pub fn effective_expiry_chrono(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if let Some(max_age) = self.max_age_secs() {
+            let max_age = Duration::try_seconds(max_age.max(0))?;
+            return now.checked_add_signed(max_age);
+        }
+
+        self.expires.to_chrono()
+    }
+
+    /// Returns if the cookie is expired at `now`, see [`Cookie::is_expired`].
+    pub fn is_expired_chrono(&self, now: DateTime<Utc>) -> bool {
+        self.effective_expiry_chrono(now)
+            .is_some_and(|expiry| expiry <= now)
+    }
+
+    /// Returns how long the cookie is valid from `now` using a [`chrono::Duration`], see
+    /// [`Cookie::remaining`].
+    pub fn remaining_chrono(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.effective_expiry_chrono(now)
+            .map(|expiry| (expiry - now).max(Duration::zero()))
     }
 }
 
@@ -43,20 +51,16 @@ impl Expires {
     pub fn remove_chrono() -> Self {
         Self::from(Utc::now() - Duration::days(365))
     }
-}
-
-pub(super) fn ser_expires(expires: &DateTime<Utc>, buf: &mut String) -> crate::Result<()> {
-    buf.push_str("; Expires=");
 
-    expires
-        .format(FMT)
-        .write_to(buf)
-        .map_err(|_| Error::ExpiresFmt)
+    /// Returns the expiry as a [`chrono::DateTime`], or `None` for session cookies.
+    pub fn to_chrono(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.unix_timestamp()?, 0)
+    }
 }
 
 #[cfg(test)]
 mod test_chrono {
-    use crate::{Cookie, cookie::expires::dep_chrono::MAX_EXPIRES};
+    use crate::Cookie;
     use chrono::{Duration, TimeZone, Utc};
 
     #[test]
@@ -75,7 +79,9 @@ mod test_chrono {
     fn large_date() {
         assert_eq!(
             Cookie::build("foo", "bar")
-                .expires(MAX_EXPIRES + Duration::weeks(1))
+                .expires(
+                    Utc.with_ymd_and_hms(9999, 12, 31, 23, 59, 59).unwrap() + Duration::weeks(1)
+                )
                 .build()
                 .serialize()
                 .as_deref(),
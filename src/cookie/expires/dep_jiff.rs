@@ -1,36 +1,61 @@
-use std::{fmt::Write, sync::LazyLock};
+use jiff::{SignedDuration, Span, Timestamp, Zoned, tz::TimeZone};
 
-use jiff::{SignedDuration, Span, Zoned, tz::TimeZone};
-
-use crate::{Cookie, Error, cookie::expires::ExpVal};
+use crate::Cookie;
 
 use super::Expires;
 
-// Sun, 06 Nov 1994 08:49:37 GMT (RFC)
-static FMT: &str = "%a, %d %b %Y %T %Z";
+impl From<Timestamp> for Expires {
+    fn from(value: Timestamp) -> Self {
+        Self::from_unix_timestamp(value.as_second())
+    }
+}
 
 impl From<Zoned> for Expires {
     fn from(value: Zoned) -> Self {
-        Self::Exp(super::ExpVal {
-            jiff: Some(value),
-            ..Default::default()
-        })
+        Self::from(value.timestamp())
+    }
+}
+
+impl From<&Zoned> for Expires {
+    fn from(value: &Zoned) -> Self {
+        Self::from(value.timestamp())
     }
 }
 
 impl Cookie {
-    /// Returns the Expires attribute using a [`Zoned`](jiff::Zoned).
-    pub fn expires_jiff(&self) -> Option<&Zoned> {
-        match &self.expires {
-            Expires::Exp(ExpVal { jiff, .. }) => jiff.as_ref(),
-            _ => None,
-        }
+    /// Returns the Expires attribute using a [`Zoned`](jiff::Zoned) in UTC.
+    pub fn expires_jiff(&self) -> Option<Zoned> {
+        self.expires.to_jiff()
     }
 
     /// Returns the Max-Age attribute using a [`SignedDuration`](jiff::SignedDuration).
     pub fn max_age_jiff(&self) -> Option<SignedDuration> {
-        self.max_age_secs()
-            .map(|max_age| SignedDuration::from_secs(max_age as i64))
+        self.max_age_secs().map(SignedDuration::from_secs)
+    }
+
+    /// Returns when the cookie expires using a [`Zoned`](jiff::Zoned) in the time zone of `now`,
+    /// see [`Cookie::effective_expiry`].
+    pub fn effective_expiry_jiff(&self, now: &Zoned) -> Option<Zoned> {
+        if let Some(max_age) = self.max_age_secs() {
+            let max_age = SignedDuration::from_secs(max_age.max(0));
+            return now.checked_add(max_age).ok();
+        }
+
+        let timestamp = self.expires.to_jiff_timestamp()?;
+        Some(timestamp.to_zoned(now.time_zone().clone()))
+    }
+
+    /// Returns if the cookie is expired at `now`, see [`Cookie::is_expired`].
+    pub fn is_expired_jiff(&self, now: &Zoned) -> bool {
+        self.effective_expiry_jiff(now)
+            .is_some_and(|expiry| expiry <= *now)
+    }
+
+    /// Returns how long the cookie is valid from `now` using a
+    /// [`SignedDuration`](jiff::SignedDuration), see [`Cookie::remaining`].
+    pub fn remaining_jiff(&self, now: &Zoned) -> Option<SignedDuration> {
+        self.effective_expiry_jiff(now)
+            .map(|expiry| now.duration_until(&expiry).max(SignedDuration::ZERO))
     }
 }
 
@@ -39,14 +64,16 @@ impl Expires {
     pub fn remove_jiff() -> Self {
         Self::from(&Zoned::now() - Span::new().years(1))
     }
-}
 
-pub(super) fn ser_expires(expires: &Zoned, buf: &mut String) -> crate::Result<()> {
-    static GMT: LazyLock<TimeZone> =
-        LazyLock::new(|| TimeZone::get("GMT").expect("Failed to look up `GMT` timezone"));
+    /// Returns the expiry as a [`Timestamp`](jiff::Timestamp), or `None` for session cookies.
+    pub fn to_jiff_timestamp(&self) -> Option<Timestamp> {
+        Timestamp::from_second(self.unix_timestamp()?).ok()
+    }
 
-    let zoned = expires.with_time_zone(GMT.clone());
-    write!(buf, "; Expires={}", zoned.strftime(FMT)).map_err(|_| Error::ExpiresFmt)
+    /// Returns the expiry as a [`Zoned`](jiff::Zoned) in UTC, or `None` for session cookies.
+    pub fn to_jiff(&self) -> Option<Zoned> {
+        Some(self.to_jiff_timestamp()?.to_zoned(TimeZone::UTC))
+    }
 }
 
 #[cfg(test)]
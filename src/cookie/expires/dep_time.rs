@@ -1,37 +1,47 @@
-use time::{
-    Duration, OffsetDateTime, UtcOffset, format_description::FormatItem, macros::format_description,
-};
+use time::{Duration, OffsetDateTime};
 
-use crate::{Cookie, cookie::expires::ExpVal};
+use crate::Cookie;
 
 use super::Expires;
 
-static FMT: &[FormatItem<'_>] = format_description!(
-    "[weekday repr:short], [day] [month repr:short] [year padding:none] [hour]:[minute]:[second] GMT"
-);
-
 impl From<OffsetDateTime> for Expires {
     fn from(value: OffsetDateTime) -> Self {
-        Self::Exp(ExpVal {
-            time: Some(value),
-            ..Default::default()
-        })
+        Self::from_unix_timestamp(value.unix_timestamp())
     }
 }
 
 impl Cookie {
     /// Returns the Expires attribute using a [`OffsetDateTime`](time::OffsetDateTime).
     pub fn expires_time(&self) -> Option<OffsetDateTime> {
-        match &self.expires {
-            Expires::Exp(ExpVal { time, .. }) => *time,
-            _ => None,
-        }
+        self.expires.to_time()
     }
 
     /// Returns the Max-Age attribute using a [`Duration`](time::Duration).
     pub fn max_age_time(&self) -> Option<Duration> {
-        self.max_age_secs()
-            .map(|max_age| Duration::seconds(max_age as i64))
+        self.max_age_secs().map(Duration::seconds)
+    }
+
+    /// Returns when the cookie expires using a [`OffsetDateTime`](time::OffsetDateTime), see
+    /// [`Cookie::effective_expiry`].
+    pub fn effective_expiry_time(&self, now: OffsetDateTime) -> Option<OffsetDateTime> {
+        if let Some(max_age) = self.max_age_secs() {
+            return now.checked_add(Duration::seconds(max_age.max(0)));
+        }
+
+        self.expires.to_time()
+    }
+
+    /// Returns if the cookie is expired at `now`, see [`Cookie::is_expired`].
+    pub fn is_expired_time(&self, now: OffsetDateTime) -> bool {
+        self.effective_expiry_time(now)
+            .is_some_and(|expiry| expiry <= now)
+    }
+
+    /// Returns how long the cookie is valid from `now` using a [`Duration`](time::Duration), see
+    /// [`Cookie::remaining`].
+    pub fn remaining_time(&self, now: OffsetDateTime) -> Option<Duration> {
+        self.effective_expiry_time(now)
+            .map(|expiry| (expiry - now).max(Duration::ZERO))
     }
 }
 
@@ -40,18 +50,12 @@ impl Expires {
     pub fn remove_time() -> Self {
         Self::from(OffsetDateTime::now_utc() - Duration::days(365))
     }
-}
 
-pub(super) fn ser_expires(expires: &OffsetDateTime, buf: &mut String) -> crate::Result<()> {
-    let expires = expires
-        .to_offset(UtcOffset::UTC)
-        .format(&FMT)
-        .map_err(|_| crate::Error::ExpiresFmt)?;
-
-    buf.push_str("; Expires=");
-    buf.push_str(&expires);
-
-    Ok(())
+    /// Returns the expiry as a UTC [`OffsetDateTime`](time::OffsetDateTime), or `None` for
+    /// session cookies.
+    pub fn to_time(&self) -> Option<OffsetDateTime> {
+        OffsetDateTime::from_unix_timestamp(self.unix_timestamp()?).ok()
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,8 @@
-use std::fmt::{Debug, Write};
+use alloc::string::String;
+use core::{
+    fmt::{self, Debug, Display, Write},
+    str::FromStr,
+};
 
 use super::Cookie;
 
@@ -11,9 +15,25 @@ pub mod dep_chrono;
 #[cfg(feature = "jiff")]
 pub mod dep_jiff;
 
+// Starting at 1 Jan 1970, a Thursday.
+const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
 const REMOVE: &str = "Thu, 01 Jan 1970 00:00:00 GMT";
 
+// The Expires attribute only has room for a four digit year, later dates are clamped to
+// Fri, 31 Dec 9999 23:59:59 GMT.
+const MAX_TIMESTAMP: i64 = 253_402_300_799;
+// User-agents ignore years before 1601, earlier dates are clamped to Mon, 01 Jan 1601 00:00:00 GMT.
+const MIN_TIMESTAMP: i64 = -11_644_473_600;
+
 /// The Expires attribute.
+///
+/// The expiry is stored as a UTC timestamp with second precision, the precision of the Expires
+/// attribute. An `Expires` created from one datetime crate can be retrieved with any of the
+/// others, see [`Expires::unix_timestamp`].
 #[derive(Clone, Default)]
 pub enum Expires {
     // So a user can still remove a cookie without needing any of the datetime features.
@@ -24,33 +44,101 @@ pub enum Expires {
     Exp(ExpVal),
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
 pub struct ExpVal {
-    #[cfg(feature = "time")]
-    time: Option<time::OffsetDateTime>,
-    #[cfg(feature = "chrono")]
-    chrono: Option<chrono::DateTime<chrono::Utc>>,
-    #[cfg(feature = "jiff")]
-    jiff: Option<jiff::Zoned>,
+    // Seconds since the Unix epoch, UTC.
+    secs: i64,
 }
 
 impl Expires {
     /// If one of the `time`, `chrono` or `jiff` features are enabled, the Expires tag is set to the
     /// current time minus one year. If none of the those features are enabled, the Expires
     /// attribute is set to 1 Jan 1970 00:00.
+    ///
+    /// The current time is read from the system clock, use `Expires::remove_with` to remove
+    /// cookies with a `Clock`.
     pub fn remove() -> Self {
-        #![allow(unreachable_code)]
+        #[cfg(any(feature = "time", feature = "chrono", feature = "jiff"))]
+        return Self::remove_with(&crate::SystemClock);
+
+        #[cfg(not(any(feature = "time", feature = "chrono", feature = "jiff")))]
+        Self::Remove
+    }
+
+    /// Creates an `Expires` from the number of seconds since the Unix epoch. Dates after the year
+    /// 9999 and before the year 1601 are clamped.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::Expires;
+    ///
+    /// let expires = Expires::from_unix_timestamp(1_445_412_480);
+    /// assert_eq!(expires.unix_timestamp(), Some(1_445_412_480));
+    /// ```
+    pub fn from_unix_timestamp(secs: i64) -> Self {
+        Self::Exp(ExpVal {
+            secs: secs.clamp(MIN_TIMESTAMP, MAX_TIMESTAMP),
+        })
+    }
+
+    /// Returns the expiry as the number of seconds since the Unix epoch, or `None` for session
+    /// cookies.
+    pub fn unix_timestamp(&self) -> Option<i64> {
+        match self {
+            Expires::Remove => Some(0),
+            Expires::Session => None,
+            Expires::Exp(ExpVal { secs }) => Some(*secs),
+        }
+    }
+}
 
-        #[cfg(feature = "jiff")]
-        return Self::remove_jiff();
+#[cfg(feature = "std")]
+impl From<std::time::SystemTime> for Expires {
+    fn from(value: std::time::SystemTime) -> Self {
+        use std::time::UNIX_EPOCH;
 
-        #[cfg(feature = "chrono")]
-        return Self::remove_chrono();
+        let secs = match value.duration_since(UNIX_EPOCH) {
+            Ok(since) => i64::try_from(since.as_secs()).unwrap_or(i64::MAX),
+            // Round down to the whole second before the epoch.
+            Err(before) => {
+                let before = before.duration();
+                let secs = before.as_secs() + u64::from(before.subsec_nanos() > 0);
+                i64::try_from(secs).map_or(i64::MIN, |secs| -secs)
+            }
+        };
 
-        #[cfg(feature = "time")]
-        return Self::remove_time();
+        Self::from_unix_timestamp(secs)
+    }
+}
 
-        Self::Remove
+#[cfg(feature = "std")]
+impl Expires {
+    /// Creates an `Expires` one year before the current time of `clock`, used to remove cookies
+    /// from the user-agent.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Expires, FixedClock};
+    ///
+    /// let clock = FixedClock::from_unix_timestamp(784_111_777);
+    /// let expires = Expires::remove_with(&clock);
+    /// assert_eq!(expires.to_string(), "Sat, 06 Nov 1993 08:49:37 GMT");
+    /// ```
+    pub fn remove_with(clock: &dyn crate::Clock) -> Self {
+        let now = Self::from(clock.now()).unix_timestamp().unwrap_or_default();
+        Self::from_unix_timestamp(now - 365 * 86_400)
+    }
+
+    /// Returns the expiry as a [`SystemTime`](std::time::SystemTime), or `None` for session
+    /// cookies.
+    pub fn to_system_time(&self) -> Option<std::time::SystemTime> {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let secs = self.unix_timestamp()?;
+        match u64::try_from(secs) {
+            Ok(secs) => UNIX_EPOCH.checked_add(Duration::from_secs(secs)),
+            Err(_) => UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs())),
+        }
     }
 }
 
@@ -61,55 +149,168 @@ impl Cookie {
         !matches!(self.expires, Expires::Session)
     }
 
+    /// Returns when the cookie expires, given that it's set or received at `now`. Returns `None`
+    /// for session cookies, or if the expiry can't be represented as a `SystemTime`.
+    ///
+    /// The Max-Age attribute takes precedence over the Expires attribute, like
+    /// [RFC 6265 §5.3](https://datatracker.ietf.org/doc/html/rfc6265#section-5.3) prescribes. A
+    /// Max-Age of 0 or less expires the cookie at `now`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// use cookie_monster::Cookie;
+    ///
+    /// let now = SystemTime::now();
+    /// let cookie = Cookie::build("session", "abc").max_age_secs(60).build();
+    ///
+    /// assert_eq!(cookie.effective_expiry(now), Some(now + Duration::from_secs(60)));
+    /// assert!(!cookie.is_expired(now));
+    /// assert_eq!(cookie.remaining(now), Some(Duration::from_secs(60)));
+    ///
+    /// assert!(Cookie::remove("session").is_expired(now));
+    /// assert_eq!(Cookie::new("session", "abc").remaining(now), None);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn effective_expiry(&self, now: std::time::SystemTime) -> Option<std::time::SystemTime> {
+        if let Some(max_age) = self.max_age() {
+            return now.checked_add(max_age);
+        }
+
+        self.expires.to_system_time()
+    }
+
+    /// Returns if the cookie is expired at `now`, see [`Cookie::effective_expiry`]. Session
+    /// cookies never expire.
+    #[cfg(feature = "std")]
+    pub fn is_expired(&self, now: std::time::SystemTime) -> bool {
+        self.effective_expiry(now)
+            .is_some_and(|expiry| expiry <= now)
+    }
+
+    /// Returns how long the cookie is valid from `now`, see [`Cookie::effective_expiry`]. Returns
+    /// a zero duration for expired cookies and `None` for session cookies.
+    #[cfg(feature = "std")]
+    pub fn remaining(&self, now: std::time::SystemTime) -> Option<core::time::Duration> {
+        self.effective_expiry(now)
+            .map(|expiry| expiry.duration_since(now).unwrap_or_default())
+    }
+
     pub(crate) fn serialize_expire(&self, buf: &mut String) -> crate::Result<()> {
-        // Only one can be set at all times, except while parsing but then the first match is used.
         match &self.expires {
-            #[cfg(feature = "time")]
-            Expires::Exp(ExpVal { time: Some(t), .. }) => dep_time::ser_expires(t, buf),
-            #[cfg(feature = "chrono")]
-            Expires::Exp(ExpVal {
-                chrono: Some(c), ..
-            }) => dep_chrono::ser_expires(c, buf),
-            #[cfg(feature = "jiff")]
-            Expires::Exp(ExpVal { jiff: Some(j), .. }) => dep_jiff::ser_expires(j, buf),
             Expires::Remove => {
                 let _ = write!(buf, "; Expires={REMOVE}");
-                Ok(())
             }
-
-            _ => Ok(()),
+            Expires::Exp(ExpVal { secs }) => {
+                buf.push_str("; Expires=");
+                let _ = write_http_date(*secs, buf);
+            }
+            Expires::Session => {}
         }
+
+        Ok(())
+    }
+}
+
+// Writes the timestamp as an IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`
+// (RFC 9110 §5.6.7).
+fn write_http_date(secs: i64, w: &mut impl Write) -> core::fmt::Result {
+    let days = secs.div_euclid(86_400);
+    let secs_of_day = secs.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    write!(
+        w,
+        "{}, {day:02} {} {year:04} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[days.rem_euclid(7) as usize],
+        MONTHS[month as usize - 1],
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    )
+}
+
+// Parses an IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT` (RFC 9110 §5.6.7). Returns the
+// seconds since the Unix epoch.
+fn parse_http_date(date: &str) -> Option<i64> {
+    fn number(digits: &str) -> Option<i64> {
+        digits.bytes().try_fold(0, |n, d| {
+            d.is_ascii_digit().then(|| n * 10 + i64::from(d - b'0'))
+        })
+    }
+
+    // Sun, 06 Nov 1994 08:49:37 GMT
+    // 0    5  8   12   17 20 23 26
+    if date.len() != 29
+        || !date.is_ascii()
+        || &date[3..5] != ", "
+        || [7, 11, 16, 25].iter().any(|&i| date.as_bytes()[i] != b' ')
+        || [19, 22].iter().any(|&i| date.as_bytes()[i] != b':')
+        || &date[26..] != "GMT"
+    {
+        return None;
+    }
+
+    let weekday = WEEKDAYS.iter().position(|w| *w == &date[..3])?;
+    let month = MONTHS.iter().position(|m| *m == &date[8..11])? as u32 + 1;
+    let day = number(&date[5..7])? as u32;
+    let year = number(&date[12..16])?;
+    let (hour, minute, second) = (
+        number(&date[17..19])?,
+        number(&date[20..22])?,
+        number(&date[23..25])?,
+    );
+
+    if day == 0 || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    // Rejects days that don't exist in the month, e.g. 31 Feb, and a weekday that doesn't match
+    // the date.
+    if civil_from_days(days) != (year, month, day) || days.rem_euclid(7) as usize != weekday {
+        return None;
     }
+
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second)
+}
+
+// Converts a date in the proleptic Gregorian calendar to days since the Unix epoch. See
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+// Converts days since the Unix epoch to a (year, month, day) date in the proleptic Gregorian
+// calendar. See http://howardhinnant.github.io/date_algorithms.html#civil_from_days.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
 }
 
 impl PartialEq for Expires {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Expires::Remove, Expires::Remove) => true,
             (Expires::Session, Expires::Session) => true,
-            (Expires::Exp(_s), Expires::Exp(_o)) => {
-                // Each `From` impl only populates the field for the datetime crate it came
-                // from, leaving the others `None`. Compare a field only when at least one
-                // side actually has it set, otherwise a shared `None` would falsely match.
-                #[cfg(feature = "time")]
-                if _s.time.is_some() || _o.time.is_some() {
-                    return _s.time == _o.time;
-                }
-
-                #[cfg(feature = "chrono")]
-                if _s.chrono.is_some() || _o.chrono.is_some() {
-                    return _s.chrono == _o.chrono;
-                }
-
-                #[cfg(feature = "jiff")]
-                if _s.jiff.is_some() || _o.jiff.is_some() {
-                    return _s.jiff == _o.jiff;
-                }
-
-                // Neither side has any datetime set; treat them as equal.
-                true
-            }
-            _ => false,
+            (Expires::Session, _) | (_, Expires::Session) => false,
+            _ => self.unix_timestamp() == other.unix_timestamp(),
         }
     }
 }
@@ -129,9 +330,8 @@ mod eq_tests {
             .build()
     }
 
-    // With more than one datetime feature enabled, only the populated field
-    // (here `jiff`) carries the expiry. Equality must not treat a shared `None`
-    // in another field (`time`) as a match.
+    // With more than one datetime feature enabled, equality must compare the
+    // expiry itself and not depend on which crate it was created with.
     #[test]
     fn different_expiry_is_not_equal() {
         assert_ne!(cookie_expiring(2020), cookie_expiring(2099));
@@ -143,25 +343,98 @@ mod eq_tests {
     }
 }
 
+/// Parses an IMF-fixdate, the date format of the Expires attribute, e.g.
+/// `Sun, 06 Nov 1994 08:49:37 GMT`.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{ErrorKind, Expires};
+///
+/// let expires: Expires = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// assert_eq!(expires.unix_timestamp(), Some(784_111_777));
+/// assert_eq!(expires.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+///
+/// let error = "Sunday, 06-Nov-94 08:49:37 GMT".parse::<Expires>().unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::InvalidDate);
+/// ```
+impl FromStr for Expires {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_http_date(s)
+            .map(Self::from_unix_timestamp)
+            .ok_or(crate::Error::parse(crate::ErrorKind::InvalidDate))
+    }
+}
+
+/// Formats the expiry as an IMF-fixdate, or `Session` for session cookies.
+impl Display for Expires {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
 impl Debug for Expires {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Remove => write!(f, "{REMOVE}"),
             Self::Session => write!(f, "Session"),
-            Self::Exp(_exp) => {
-                let mut debug = f.debug_struct("Exp");
+            Self::Exp(ExpVal { secs }) => write_http_date(*secs, f),
+        }
+    }
+}
 
-                #[cfg(feature = "time")]
-                let debug = debug.field("expires_time", &_exp.time);
+#[cfg(test)]
+mod http_date_tests {
+    use alloc::string::String;
 
-                #[cfg(feature = "chrono")]
-                let debug = debug.field("expires_chrono", &_exp.chrono);
+    use super::{MAX_TIMESTAMP, MIN_TIMESTAMP, parse_http_date, write_http_date};
 
-                #[cfg(feature = "jiff")]
-                let debug = debug.field("expires_jiff", &_exp.jiff);
+    fn format(secs: i64) -> String {
+        let mut buf = String::new();
+        write_http_date(secs, &mut buf).unwrap();
+        buf
+    }
 
-                debug.finish()
-            }
+    #[test]
+    fn format_dates() {
+        assert_eq!(format(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(format(784_111_777), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(format(951_782_400), "Tue, 29 Feb 2000 00:00:00 GMT");
+        assert_eq!(format(-1), "Wed, 31 Dec 1969 23:59:59 GMT");
+        assert_eq!(format(MAX_TIMESTAMP), "Fri, 31 Dec 9999 23:59:59 GMT");
+        assert_eq!(format(MIN_TIMESTAMP), "Mon, 01 Jan 1601 00:00:00 GMT");
+    }
+
+    #[test]
+    fn parse_dates() {
+        for secs in [
+            0,
+            784_111_777,
+            951_782_400,
+            -1,
+            MAX_TIMESTAMP,
+            MIN_TIMESTAMP,
+        ] {
+            assert_eq!(parse_http_date(&format(secs)), Some(secs));
+        }
+
+        for invalid in [
+            "",
+            "Sun, 06 Nov 1994 08:49:37 UTC",
+            "Sun, 06 Nov 1994 08:49:37 GMT ",
+            "Sun,  6 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+            "Mon, 06 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 24:00:00 GMT",
+            "Sun, 06 Nov 1994 08:60:37 GMT",
+            "Thu, 31 Feb 2000 00:00:00 GMT",
+            "Sun, 00 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Xyz 1994 08:49:37 GMT",
+            "Sun, 06 Nov 19+4 08:49:37 GMT",
+        ] {
+            assert_eq!(parse_http_date(invalid), None, "{invalid}");
         }
     }
 }
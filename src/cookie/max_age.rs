@@ -0,0 +1,96 @@
+use core::time::Duration;
+
+/// The value of the Max-Age attribute, in seconds.
+///
+/// Zero and negative values expire the cookie immediately. Negative values are serialized as is,
+/// e.g. `Max-Age=-1`, so cookies from an upstream server can be relayed faithfully.
+///
+/// `MaxAge` can be created from a [`Duration`](core::time::Duration) and, with the datetime
+/// features, from `time::Duration`, `chrono::TimeDelta` and `jiff::SignedDuration`, so these can be
+/// passed to [`CookieBuilder::max_age`](crate::CookieBuilder::max_age) directly. A `jiff::Span`
+/// converts with `MaxAge::try_from`, which fails if the span has years or months.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use cookie_monster::{Cookie, MaxAge};
+///
+/// let cookie = Cookie::build("foo", "bar")
+///     .max_age(Duration::from_secs(60))
+///     .build();
+/// assert_eq!(cookie.max_age_secs(), Some(60));
+///
+/// assert_eq!(MaxAge::from(Duration::from_millis(1500)).as_secs(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MaxAge(i64);
+
+impl MaxAge {
+    /// A Max-Age of 0 seconds, expires the cookie immediately.
+    pub const ZERO: MaxAge = MaxAge(0);
+
+    /// Creates a `MaxAge` from the given number of seconds.
+    pub const fn from_secs(secs: i64) -> Self {
+        Self(secs)
+    }
+
+    /// Returns the number of seconds.
+    pub const fn as_secs(self) -> i64 {
+        self.0
+    }
+
+    // Saturates at `i64::MAX` seconds, usable in const contexts.
+    pub(crate) const fn secs_of(duration: Duration) -> i64 {
+        let secs = duration.as_secs();
+        if secs > i64::MAX as u64 {
+            i64::MAX
+        } else {
+            secs as i64
+        }
+    }
+}
+
+/// Saturates at `i64::MAX` seconds, sub-second precision is dropped.
+impl From<Duration> for MaxAge {
+    fn from(value: Duration) -> Self {
+        Self(Self::secs_of(value))
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::Duration> for MaxAge {
+    fn from(value: time::Duration) -> Self {
+        Self(value.whole_seconds())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::TimeDelta> for MaxAge {
+    fn from(value: chrono::TimeDelta) -> Self {
+        Self(value.num_seconds())
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl From<jiff::SignedDuration> for MaxAge {
+    fn from(value: jiff::SignedDuration) -> Self {
+        Self(value.as_secs())
+    }
+}
+
+/// Days and weeks are 24 hours and 7 days long. Spans with years or months are rejected, their
+/// length depends on the date they are measured from.
+#[cfg(feature = "jiff")]
+impl TryFrom<jiff::Span> for MaxAge {
+    type Error = jiff::Error;
+
+    fn try_from(value: jiff::Span) -> Result<Self, Self::Error> {
+        use jiff::{SpanRelativeTo, Unit};
+
+        let secs = value.total((Unit::Second, SpanRelativeTo::days_are_24_hours()))?;
+
+        // Float to int casts saturate.
+        Ok(Self(secs as i64))
+    }
+}
@@ -1,45 +1,67 @@
-use std::{
-    borrow::Cow,
+use alloc::borrow::Cow;
+use core::{
     fmt::{self, Debug},
     time::Duration,
 };
 
+mod borrowed;
 mod builder;
+pub(crate) mod category;
 mod domain;
 pub(crate) mod expires;
+mod max_age;
+pub(crate) mod options;
 mod parse;
 mod path;
+pub(crate) mod policy;
 pub(crate) mod prefix;
 pub(crate) mod same_site;
 mod serialize;
+mod template;
 
 #[cfg(feature = "percent-encode")]
-mod encoding;
+pub(crate) mod encoding;
 
+pub use borrowed::CookieRef;
 pub use builder::CookieBuilder;
 use expires::Expires;
+pub use max_age::MaxAge;
 use prefix::CookiePrefix;
+pub use template::CookieTemplate;
 
-use crate::{SameSite, util::TinyStr};
+use crate::{
+    CookieCategory, SameSite,
+    util::{RawBuf, TinyStr},
+};
 
 /// An HTTP Cookie.
 #[derive(Default, Clone)]
 pub struct Cookie {
-    // A read only buffer to the raw cookie value. We're using a Box<str> here since it makes the
-    // Cookie a bit smaller.
-    raw_value: Option<Box<str>>,
+    // A read only buffer to the raw cookie value. Cookies parsed from the same `Cookie` header share
+    // a single buffer, so parsing a header only allocates once.
+    raw_value: Option<RawBuf>,
     name: TinyStr,
     value: TinyStr,
     expires: Expires,
-    max_age: Option<u64>,
+    max_age: Option<i64>,
     domain: Option<TinyStr>,
     path: Option<TinyStr>,
-    secure: bool,
-    http_only: bool,
-    partitioned: bool,
+    // `None` if the attribute was never set, so a `CookiePolicy` can supply a default.
+    secure: Option<bool>,
+    http_only: Option<bool>,
+    partitioned: Option<bool>,
     same_site: Option<SameSite>,
     // The recognized name prefix, detected from `name`. Kept in sync whenever the name changes.
     prefix: Option<CookiePrefix>,
+    // If the parsed value was surrounded by double quotes. The quotes are not part of `value`.
+    parsed_quoted: bool,
+    // If the value is surrounded by double quotes when serializing, only set with `set_quoted`.
+    quoted: bool,
+    // Set by a jar in dev mode, the Secure attribute is never written. Not even for SameSite=None
+    // and Partitioned cookies.
+    insecure: bool,
+    // Only used by a jar to filter cookies by consent, never sent to the user-agent.
+    category: CookieCategory,
 }
 
 impl Cookie {
@@ -88,17 +110,53 @@ impl Cookie {
     /// assert_eq!(cookie.max_age_secs(), Some(0));
     /// assert!(cookie.expires_is_set());
     /// ```
+    ///
+    /// The Expires attribute is based on the system clock, use `Cookie::remove_with` to use a
+    /// `Clock` instead.
     pub fn remove<N>(name: N) -> Cookie
     where
         N: Into<Cow<'static, str>>,
     {
-        Cookie::new(name, "").into_remove()
+        Cookie::new(name, "").into_remove(Expires::remove())
+    }
+
+    /// Creates a cookie that can be used to remove the cookie from the user-agent, like
+    /// [`Cookie::remove`]. The Expires attribute is set to one year before the current time of
+    /// `clock`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, FixedClock};
+    ///
+    /// let clock = FixedClock::from_unix_timestamp(784_111_777);
+    /// let cookie = Cookie::remove_with("session", &clock);
+    ///
+    /// assert_eq!(
+    ///     cookie.serialize().as_deref(),
+    ///     Ok("session=; Max-Age=0; Expires=Sat, 06 Nov 1993 08:49:37 GMT")
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn remove_with<N>(name: N, clock: &dyn crate::Clock) -> Cookie
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        Cookie::new(name, "").into_remove(Expires::remove_with(clock))
     }
 
-    pub(crate) fn into_remove(mut self) -> Self {
-        self.set_expires(Expires::remove());
+    pub(crate) fn into_remove(mut self, expires: Expires) -> Self {
+        self.set_expires(expires);
         self.set_max_age_secs(0);
         self.set_value("");
+        self.set_quoted(false);
+        self
+    }
+
+    // Strips the Secure attribute and the name prefix, used by a jar in dev mode.
+    pub(crate) fn into_insecure(mut self) -> Self {
+        self.secure = Some(false);
+        self.prefix = None;
+        self.insecure = true;
         self
     }
 
@@ -167,12 +225,48 @@ impl Cookie {
         self.name = TinyStr::from(name)
     }
 
-    /// Get the cookie value. This does not trim `"` characters.
+    /// Get the cookie value.
+    ///
+    /// The double quotes around a parsed value are removed, use [`Cookie::is_quoted`] to find out
+    /// if the value was quoted.
     #[inline]
     pub fn value(&self) -> &str {
         self.value.as_str(self.raw_value.as_deref())
     }
 
+    /// Returns if the value is surrounded by double quotes, either because the parsed value was
+    /// quoted, e.g. `name="value"`, or because quoting was enabled with
+    /// [`set_quoted`](Self::set_quoted).
+    ///
+    /// The quotes of a parsed value are not written when serializing, call `set_quoted(true)` to
+    /// re-quote it. Quoting is not taken into account when comparing cookies.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::Cookie;
+    ///
+    /// let mut cookie = Cookie::parse_cookie("name=\"value\"").unwrap();
+    ///
+    /// assert_eq!(cookie.value(), "value");
+    /// assert!(cookie.is_quoted());
+    /// assert_eq!(cookie.serialize().as_deref(), Ok("name=value"));
+    ///
+    /// cookie.set_quoted(true);
+    /// assert_eq!(cookie.serialize().as_deref(), Ok("name=\"value\""));
+    /// ```
+    #[inline]
+    pub fn is_quoted(&self) -> bool {
+        self.quoted || self.parsed_quoted
+    }
+
+    /// Sets if the value should be surrounded by double quotes when serializing. The value itself
+    /// should not contain the quotes.
+    #[inline]
+    pub fn set_quoted(&mut self, quoted: bool) {
+        self.quoted = quoted;
+        self.parsed_quoted = false;
+    }
+
     /// Set the cookie value.
     #[inline]
     pub fn set_value<V: Into<Cow<'static, str>>>(&mut self, value: V) {
@@ -185,30 +279,32 @@ impl Cookie {
         self.expires = expires.into();
     }
 
-    /// Get the Max-Age duration. This returns a [`std::time::Duration`].
+    /// Get the Max-Age duration. This returns a [`core::time::Duration`], negative values are
+    /// returned as [`Duration::ZERO`], use [`Cookie::max_age_secs`] for the signed value.
     ///
     /// If you'd like a `time`, `chrono` or `jiff` specific duration use the
     /// `max_age_{time,chrono,jiff}` methods.
     #[inline]
     pub fn max_age(&self) -> Option<Duration> {
-        self.max_age.map(Duration::from_secs)
+        self.max_age
+            .map(|max_age| Duration::from_secs(max_age.max(0).unsigned_abs()))
     }
 
-    /// Get the Max-Age as seconds.
+    /// Get the Max-Age as seconds. Zero and negative values expire the cookie immediately.
     #[inline]
-    pub fn max_age_secs(&self) -> Option<u64> {
+    pub fn max_age_secs(&self) -> Option<i64> {
         self.max_age
     }
 
-    /// Set the Max-Age attribute.
+    /// Set the Max-Age attribute, see [`MaxAge`] for the types that can be used.
     #[inline]
-    pub fn set_max_age(&mut self, max_age: Duration) {
-        self.set_max_age_secs(max_age.as_secs());
+    pub fn set_max_age(&mut self, max_age: impl Into<MaxAge>) {
+        self.set_max_age_secs(max_age.into().as_secs());
     }
 
     /// Set the Max-Age value in seconds.
     #[inline]
-    pub fn set_max_age_secs(&mut self, max_age_secs: u64) {
+    pub fn set_max_age_secs(&mut self, max_age_secs: i64) {
         self.max_age = Some(max_age_secs);
     }
 
@@ -265,37 +361,37 @@ impl Cookie {
     /// Returns if the Secure attribute is set.
     #[inline]
     pub fn is_secure(&self) -> bool {
-        self.secure
+        self.secure.unwrap_or(false)
     }
 
     /// Sets the Secure attribute of the cookie.
     #[inline]
     pub fn set_secure(&mut self, secure: bool) {
-        self.secure = secure
+        self.secure = Some(secure)
     }
 
     /// Returns if the HttpOnly attribute is set.
     #[inline]
     pub fn is_http_only(&self) -> bool {
-        self.http_only
+        self.http_only.unwrap_or(false)
     }
 
     /// Sets the HttpOnly attribute of the cookie.
     #[inline]
     pub fn set_http_only(&mut self, http_only: bool) {
-        self.http_only = http_only
+        self.http_only = Some(http_only)
     }
 
     /// Returns if the Partitioned attribute is set.
     #[inline]
     pub fn is_partitioned(&self) -> bool {
-        self.partitioned
+        self.partitioned.unwrap_or(false)
     }
 
     /// Set the Partitioned flag, enabling the Partitioned attribute also enables the Secure Attribute.
     #[inline]
     pub fn set_partitioned(&mut self, partitioned: bool) {
-        self.partitioned = partitioned;
+        self.partitioned = Some(partitioned);
     }
 
     /// Returns the SameSite attribute if it is set.
@@ -309,6 +405,30 @@ impl Cookie {
     pub fn set_same_site<S: Into<Option<SameSite>>>(&mut self, same_site: S) {
         self.same_site = same_site.into();
     }
+
+    /// Returns the consent category of the cookie, see [`CookieCategory`].
+    #[inline]
+    pub fn category(&self) -> CookieCategory {
+        self.category
+    }
+
+    /// Set the consent category of the cookie. The category is not compared when comparing
+    /// cookies.
+    #[inline]
+    pub fn set_category(&mut self, category: CookieCategory) {
+        self.category = category;
+    }
+}
+
+impl Cookie {
+    /// Returns if a user-agent would store both cookies as the same cookie, meaning they have the
+    /// same name, prefix, domain and path.
+    pub(crate) fn same_identity(&self, other: &Cookie) -> bool {
+        self.name() == other.name()
+            && self.prefix == other.prefix
+            && opt_str_eq(self.domain_sanitized(), other.domain_sanitized())
+            && self.path() == other.path()
+    }
 }
 
 impl fmt::Display for Cookie {
@@ -324,7 +444,7 @@ impl Debug for Cookie {
         debug
             .field("name", &self.name())
             .field("value", &self.value())
-            .field("max_age", &self.max_age())
+            .field("max_age", &self.max_age_secs())
             .field("domain", &self.domain())
             .field("path", &self.path())
             .field("secure", &self.is_secure())
@@ -333,6 +453,8 @@ impl Debug for Cookie {
             .field("expires", &self.expires)
             .field("same_site", &self.same_site)
             .field("prefix", &self.prefix)
+            .field("quoted", &self.is_quoted())
+            .field("category", &self.category)
             .finish()
     }
 }
@@ -344,7 +466,7 @@ impl PartialEq<Cookie> for Cookie {
             || self.is_secure() != other.is_secure()
             || self.is_http_only() != other.is_http_only()
             || self.is_partitioned() != other.is_partitioned()
-            || self.max_age() != other.max_age()
+            || self.max_age_secs() != other.max_age_secs()
             || self.same_site() != other.same_site()
             || self.expires != other.expires
             || self.prefix != other.prefix
@@ -0,0 +1,97 @@
+/// Options for parsing `Cookie` headers.
+///
+/// With the `axum` feature, add `ParseOptions` as a request extension to configure how the
+/// `CookieJar` is extracted.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{Cookie, CookieJar, NonUtf8, ParseOptions, ParseProfile};
+///
+/// let header = b"session=abc; tracker=caf\xe9";
+///
+/// // By default, only the cookie that isn't valid UTF-8 is skipped.
+/// let jar = CookieJar::from_cookie_bytes(header, &ParseOptions::new());
+/// assert_eq!(jar.get("session").map(Cookie::value), Some("abc"));
+/// assert!(jar.get("tracker").is_none());
+///
+/// let options = ParseOptions::new().non_utf8(NonUtf8::Latin1);
+/// let jar = CookieJar::from_cookie_bytes(header, &options);
+/// assert_eq!(jar.get("tracker").map(Cookie::value), Some("café"));
+///
+/// // The strict profile only accepts ASCII values.
+/// let options = options.profile(ParseProfile::Strict);
+/// let jar = CookieJar::from_cookie_bytes(header, &options);
+/// assert!(jar.get("tracker").is_none());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub(crate) profile: ParseProfile,
+    pub(crate) non_utf8: NonUtf8,
+}
+
+/// Decides which characters are accepted in the name and value of a cookie.
+///
+/// All profiles trim whitespace around the name and value, and strip the double quotes around a
+/// quoted value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseProfile {
+    /// Accepts the characters that [`Cookie::serialize`](crate::Cookie::serialize) accepts. The
+    /// name must be a non-empty token, where alphanumeric characters may be non-ASCII. The value
+    /// may contain any character except controls, whitespace, double quotes, commas, semicolons
+    /// and backslashes.
+    #[default]
+    Lenient,
+    /// The grammar of [RFC 6265 §4.1.1](https://datatracker.ietf.org/doc/html/rfc6265#section-4.1.1).
+    /// The name must be a non-empty token of ASCII characters and the value may only contain
+    /// ASCII characters, excluding controls, whitespace, double quotes, commas, semicolons and
+    /// backslashes.
+    Strict,
+    /// The parsing algorithm of
+    /// [RFC 6265bis §5.6](https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-rfc6265bis#section-5.6).
+    /// The name and value may contain any character except controls, horizontal tabs are
+    /// allowed. The name may be empty, for example `=value` or a pair without a `=`, only a pair
+    /// with both an empty name and an empty value is rejected.
+    Rfc6265bis,
+    /// Accepts what Chrome and Firefox accept. These browsers implement
+    /// [`ParseProfile::Rfc6265bis`], so this currently accepts the same cookies. Use this profile
+    /// to follow the browsers if they deviate from the draft.
+    Browser,
+}
+
+/// Decides what happens with cookies in a `Cookie` header that are not valid UTF-8.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NonUtf8 {
+    /// Skip the cookies that are not valid UTF-8, the other cookies in the header are still
+    /// parsed.
+    #[default]
+    Skip,
+    /// Decode every byte as a Latin-1 (ISO-8859-1) character.
+    Latin1,
+    /// Decode the bytes as UTF-8, replacing invalid sequences with `U+FFFD`.
+    LossyUtf8,
+}
+
+impl ParseOptions {
+    /// Creates the default `ParseOptions`.
+    pub const fn new() -> Self {
+        Self {
+            profile: ParseProfile::Lenient,
+            non_utf8: NonUtf8::Skip,
+        }
+    }
+
+    /// Sets the profile that decides which characters are accepted in the name and value of a
+    /// cookie. Defaults to [`ParseProfile::Lenient`].
+    pub const fn profile(mut self, profile: ParseProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets how cookies that are not valid UTF-8 are handled. Defaults to [`NonUtf8::Skip`].
+    pub const fn non_utf8(mut self, non_utf8: NonUtf8) -> Self {
+        self.non_utf8 = non_utf8;
+        self
+    }
+}
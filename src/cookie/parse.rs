@@ -1,6 +1,14 @@
-use std::borrow::Cow;
+use alloc::{borrow::Cow, boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::ops::Range;
 
-use crate::{Cookie, error::Error, util::TinyStr};
+#[cfg(feature = "percent-encode")]
+use crate::PercentCodec;
+use crate::{
+    Cookie, CookieCodec, NonUtf8, ParseOptions, ParseProfile, RawCodec,
+    codec::decode_name_value,
+    error::{Error, ErrorKind},
+    util::{RawBuf, TinyStr},
+};
 
 impl Cookie {
     /// Parses the given cookie header value. Errors when:
@@ -9,11 +17,12 @@ impl Cookie {
     /// * The name contains an invalid character.
     /// * The cookie value contains an invalid character.
     ///
+    /// The name and value are validated with [`ParseProfile::Lenient`], use
+    /// [`Cookie::parse_cookie_with_options`] to accept more characters.
+    ///
     /// Since this only parses a cookie header value, it does not parse any cookie attributes.
     pub fn parse_cookie(string: impl Into<Box<str>>) -> crate::Result<Cookie> {
-        Self::parse_inner(string.into(), |name, value| {
-            Ok((Cow::Borrowed(name), Cow::Borrowed(value)))
-        })
+        Self::parse_cookie_with(string, &RawCodec)
     }
 
     /// Parses a percent encoded cookie value. Errors when:
@@ -25,95 +34,304 @@ impl Cookie {
     /// Since this only parses a cookie header value, it does not parse any cookie attributes.
     #[cfg(feature = "percent-encode")]
     pub fn parse_cookie_encoded(string: impl Into<Box<str>>) -> crate::Result<Cookie> {
-        use crate::cookie::encoding;
+        Self::parse_cookie_with(string, &PercentCodec)
+    }
+
+    /// Parses a cookie value and decodes the name and value with the given [`CookieCodec`].
+    /// Errors for the same reasons as [`Cookie::parse_cookie`], or when decoding fails.
+    ///
+    /// Since this only parses a cookie header value, it does not parse any cookie attributes.
+    pub fn parse_cookie_with(
+        string: impl Into<Box<str>>,
+        codec: &dyn CookieCodec,
+    ) -> crate::Result<Cookie> {
+        Self::parse_inner(string.into(), &ParseOptions::new(), codec)
+    }
 
-        Self::parse_inner(string.into(), encoding::decode_name_value)
+    /// Parses the given cookie header value with the given [`ParseOptions`]. The
+    /// [`ParseProfile`] decides which characters are accepted in the name and value.
+    ///
+    /// Since this only parses a cookie header value, it does not parse any cookie attributes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, ParseOptions, ParseProfile};
+    ///
+    /// assert!(Cookie::parse_cookie("name=hello world").is_err());
+    ///
+    /// let options = ParseOptions::new().profile(ParseProfile::Browser);
+    /// let cookie = Cookie::parse_cookie_with_options("name=hello world", &options).unwrap();
+    /// assert_eq!(cookie.value(), "hello world");
+    /// ```
+    pub fn parse_cookie_with_options(
+        string: impl Into<Box<str>>,
+        options: &ParseOptions,
+    ) -> crate::Result<Cookie> {
+        Self::parse_inner(string.into(), options, &RawCodec)
     }
 
     fn parse_inner(
-        mut string: Box<str>,
-        callback: impl for<'a> Fn(&'a str, &'a str) -> crate::Result<(Cow<'a, str>, Cow<'a, str>)>,
-    ) -> Result<Cookie, Error> {
-        let mut parts = SplitMut::new(&mut string);
+        string: Box<str>,
+        options: &ParseOptions,
+        codec: &dyn CookieCodec,
+    ) -> crate::Result<Cookie> {
+        // Only the first `name=value` pair is parsed.
+        let end = string.find(';').unwrap_or(string.len());
 
-        let name_value = parts.next().expect("First split always returns something");
+        let cookie = Self::parse_pair(&string, 0..end, options.profile, &|_| codec)?;
+        Ok(cookie.with_buffer(|| RawBuf::Owned(string)))
+    }
 
-        // 2.  If the name-value-pair string lacks a %x3D ("=") character,
-        //     ignore the set-cookie-string entirely.
-        let Some(index) = name_value.find('=') else {
-            return Err(Error::EqualsNotFound);
-        };
+    /// Parses every cookie in a `Cookie` header. The header is copied into a single buffer that is
+    /// shared by all the parsed cookies.
+    pub(crate) fn parse_header(header: &str) -> impl Iterator<Item = crate::Result<Cookie>> {
+        Self::parse_header_with(header, &const { ParseOptions::new() }, |_| &RawCodec)
+    }
+
+    /// Parses and percent-decodes every cookie in a `Cookie` header. The header is copied into a
+    /// single buffer that is shared by all the parsed cookies.
+    #[cfg(feature = "percent-encode")]
+    pub(crate) fn parse_header_encoded(
+        header: &str,
+    ) -> impl Iterator<Item = crate::Result<Cookie>> {
+        Self::parse_header_with(header, &const { ParseOptions::new() }, |_| &PercentCodec)
+    }
+
+    /// Parses every cookie in a `Cookie` header with the given options, decoding each cookie with
+    /// the codec returned by `codec_for` for its name.
+    pub(crate) fn parse_header_with<'c>(
+        header: &str,
+        options: &ParseOptions,
+        codec_for: impl Fn(&str) -> &'c dyn CookieCodec,
+    ) -> impl Iterator<Item = crate::Result<Cookie>> {
+        let buf = Arc::<str>::from(header);
+        let profile = options.profile;
 
-        // 4.  Remove any leading or trailing WSP characters from the name
-        //     string and the value string.
-        let name = name_value[..index].trim();
-        let mut value = name_value[(index + 1)..].trim();
+        header.split(';').map(move |name_value| {
+            let start = name_value.as_ptr() as usize - header.as_ptr() as usize;
+            let range = start..(start + name_value.len());
+            let cookie = Self::parse_pair(&buf, range, profile, &codec_for)?;
+            Ok(cookie.with_buffer(|| RawBuf::Shared(Arc::clone(&buf))))
+        })
+    }
 
-        // 5.  If the name string is empty, ignore the set-cookie-string entirely.
-        if name.is_empty() {
-            return Err(Error::NameEmpty);
-        } else if let Some(token) = invalid_token(name) {
-            return Err(Error::InvalidName(token));
+    /// Parses every cookie in a `Cookie` header that may not be valid UTF-8. Cookies that are not
+    /// valid UTF-8 are decoded or skipped according to the options, the other cookies are parsed
+    /// as usual. The results are in header order and error offsets point into `header`.
+    pub(crate) fn parse_header_bytes<'c>(
+        header: &[u8],
+        options: &ParseOptions,
+        codec_for: impl Fn(&str) -> &'c dyn CookieCodec,
+    ) -> Vec<crate::Result<Cookie>> {
+        if let Ok(header) = core::str::from_utf8(header) {
+            return Self::parse_header_with(header, options, codec_for).collect();
         }
 
-        // Remove optional brackets.
-        value = trim_quotes(value);
+        let mut decoded = String::with_capacity(header.len());
+        // Every fragment between two `;` with its offset in the header, `None` if it was skipped.
+        let mut fragments = Vec::new();
+        let mut offset = 0;
+        let mut first = true;
+
+        for fragment in header.split(|b| *b == b';') {
+            let start = offset;
+            offset += fragment.len() + 1;
+
+            let text = match (core::str::from_utf8(fragment), options.non_utf8) {
+                (Ok(valid), _) => Cow::Borrowed(valid),
+                (Err(e), NonUtf8::Skip) => {
+                    fragments.push((start + e.valid_up_to(), None));
+                    continue;
+                }
+                (Err(_), NonUtf8::Latin1) => fragment.iter().map(|b| *b as char).collect(),
+                (Err(_), NonUtf8::LossyUtf8) => String::from_utf8_lossy(fragment),
+            };
 
-        if let Some(invalid_char) = find_invalid_cookie_value(value) {
-            return Err(Error::InvalidValue(invalid_char));
+            if !first {
+                decoded.push(';');
+            }
+            first = false;
+            fragments.push((start, Some((decoded.len(), fragment))));
+            decoded.push_str(&text);
         }
 
+        let non_utf8 = options.non_utf8;
+        let mut cookies = Self::parse_header_with(&decoded, options, codec_for);
+
+        fragments
+            .into_iter()
+            .map(|(start, fragment)| {
+                let Some((decoded_start, bytes)) = fragment else {
+                    return Err(Error::parse(ErrorKind::InvalidUtf8).with_offset(start));
+                };
+
+                // Every fragment is parsed as exactly one cookie.
+                let result = cookies.next().expect("a result for every decoded fragment");
+                result.map_err(|error| match error.offset() {
+                    Some(offset) => {
+                        let offset = offset.saturating_sub(decoded_start);
+                        let text = &decoded[decoded_start..];
+                        let offset = original_offset(bytes, text, offset, non_utf8);
+                        error.with_offset(start + offset)
+                    }
+                    None => error,
+                })
+            })
+            .collect()
+    }
+
+    // Parses the `name=value` pair in the given range of the buffer. The name and value may point
+    // into the buffer, see `with_buffer`.
+    fn parse_pair<'c>(
+        buf: &str,
+        range: Range<usize>,
+        profile: ParseProfile,
+        codec_for: &impl Fn(&str) -> &'c dyn CookieCodec,
+    ) -> Result<Cookie, Error> {
+        let offset = range.start;
+        let (name, value, quoted) =
+            split_name_value(&buf[range], profile).map_err(|e| e.shift_offset(offset))?;
+
+        // The codec is picked by the logical (unprefixed) name.
+        let (_, logical_name) = crate::cookie::prefix::split_prefix(Cow::Borrowed(name));
+        let codec = codec_for(&logical_name);
+
         // Optionally decode the name and value.
-        let (name, value) = callback(name, value)?;
+        let (name, value) = decode_name_value(codec, name, value).map_err(|e| e.with_name(name))?;
 
         // Strip a recognized `__Host-` / `__Secure-` prefix off the name and remember it.
         let (prefix, name) = crate::cookie::prefix::split_prefix(name);
 
-        let name = TinyStr::from_cow_ref(name, parts.ptr);
-        let value = TinyStr::from_cow_ref(value, parts.ptr);
+        let name = TinyStr::from_cow_ref(name, buf.as_ptr());
+        let value = TinyStr::from_cow_ref(value, buf.as_ptr());
 
         let mut cookie = Cookie::new_inner(name, value);
         cookie.prefix = prefix;
-        cookie.raw_value = Some(string);
+        cookie.parsed_quoted = quoted;
         Ok(cookie)
     }
-}
 
-struct SplitMut<'s> {
-    haystack: Option<&'s mut str>,
-    ptr: *const u8,
+    // Only holds on to the buffer if the cookie points into it.
+    fn with_buffer(mut self, buf: impl FnOnce() -> RawBuf) -> Self {
+        if matches!(self.name, TinyStr::Indexed(..)) || matches!(self.value, TinyStr::Indexed(..)) {
+            self.raw_value = Some(buf());
+        }
+        self
+    }
 }
 
-impl<'s> SplitMut<'s> {
-    pub fn new(haystack: &'s mut str) -> SplitMut<'s> {
-        SplitMut {
-            ptr: haystack.as_ptr(),
-            haystack: Some(haystack),
+/// Splits a single `name=value` pair and validates the name and value according to the profile.
+/// Also returns if the value was surrounded by double quotes, which are removed.
+pub(crate) fn split_name_value(
+    name_value: &str,
+    profile: ParseProfile,
+) -> crate::Result<(&str, &str, bool)> {
+    // 2.  If the name-value-pair string lacks a %x3D ("=") character,
+    //     ignore the set-cookie-string entirely.
+    let Some(index) = name_value.find('=') else {
+        // RFC 6265bis and browsers treat a non-empty pair without a "=" as a cookie without a
+        // name.
+        let untrimmed = name_value.trim();
+        let value = trim_quotes(untrimmed);
+
+        if profile.allows_empty_name() && !value.is_empty() {
+            // The empty name must point into the input, since it may be indexed.
+            let name = &name_value[..0];
+            validate_value(name_value, name, value, profile)?;
+            return Ok((name, value, value.len() != untrimmed.len()));
         }
+        return Err(Error::parse(ErrorKind::EqualsNotFound));
+    };
+
+    // 4.  Remove any leading or trailing WSP characters from the name
+    //     string and the value string.
+    let name = name_value[..index].trim();
+    let untrimmed = name_value[(index + 1)..].trim();
+
+    // 5.  If the name string is empty, ignore the set-cookie-string entirely. RFC 6265bis only
+    //     ignores it if the value is empty too.
+    if name.is_empty() && (!profile.allows_empty_name() || untrimmed.is_empty()) {
+        return Err(Error::parse(ErrorKind::NameEmpty).with_offset(index));
+    } else if let Some((i, token)) = profile.invalid_name_char(name) {
+        return Err(Error::parse(ErrorKind::InvalidName(token))
+            .with_name(name)
+            .with_offset(offset_of(name_value, name) + i));
     }
-}
 
-impl<'s> Iterator for SplitMut<'s> {
-    type Item = &'s mut str;
+    // Remove optional brackets.
+    let value = trim_quotes(untrimmed);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let remainder = self.haystack.take()?;
+    validate_value(name_value, name, value, profile)?;
 
-        match remainder.find(';') {
-            Some(index) => {
-                let (current, rest) = remainder.split_at_mut(index);
+    Ok((name, value, value.len() != untrimmed.len()))
+}
 
-                if !rest.is_empty() {
-                    self.haystack = Some(&mut rest[1..]);
-                } else {
-                    self.haystack = Some(&mut rest[..]);
-                }
+fn validate_value(
+    name_value: &str,
+    name: &str,
+    value: &str,
+    profile: ParseProfile,
+) -> crate::Result<()> {
+    match profile.invalid_value_char(value) {
+        Some((i, invalid_char)) => Err(Error::parse(ErrorKind::InvalidValue(invalid_char))
+            .with_name(name)
+            .with_offset(offset_of(name_value, value) + i)),
+        None => Ok(()),
+    }
+}
+
+impl ParseProfile {
+    // If a cookie may have an empty name, like `=value` or `value`.
+    fn allows_empty_name(self) -> bool {
+        matches!(self, ParseProfile::Rfc6265bis | ParseProfile::Browser)
+    }
 
-                Some(current)
+    fn invalid_name_char(self, name: &str) -> Option<(usize, char)> {
+        match self {
+            ParseProfile::Lenient => invalid_token(name),
+            ParseProfile::Strict => name.char_indices().find(|(_, c)| !is_tchar(*c)),
+            ParseProfile::Rfc6265bis | ParseProfile::Browser => {
+                name.char_indices().find(|(_, c)| is_rfc6265bis_invalid(*c))
             }
-            None => Some(remainder),
         }
     }
+
+    fn invalid_value_char(self, value: &str) -> Option<(usize, char)> {
+        match self {
+            ParseProfile::Lenient => find_invalid_cookie_value(value),
+            ParseProfile::Strict => value.char_indices().find(|(_, c)| !is_cookie_octet(*c)),
+            ParseProfile::Rfc6265bis | ParseProfile::Browser => value
+                .char_indices()
+                .find(|(_, c)| is_rfc6265bis_invalid(*c)),
+        }
+    }
+}
+
+// token = 1*<any CHAR except CTLs or separators>, RFC 2616 §2.2.
+#[inline]
+fn is_tchar(c: char) -> bool {
+    matches!(
+        c,
+        '!' | '#' | '$' | '%' | '&' | '\'' | '*' | '+' | '-' | '.' | '^' | '_' | '`' | '|' | '~'
+    ) || c.is_ascii_alphanumeric()
+}
+
+// cookie-octet = %x21 / %x23-2B / %x2D-3A / %x3C-5B / %x5D-7E, RFC 6265 §4.1.1.
+#[inline]
+fn is_cookie_octet(c: char) -> bool {
+    matches!(c, '\x21' | '\x23'..='\x2B' | '\x2D'..='\x3A' | '\x3C'..='\x5B' | '\x5D'..='\x7E')
+}
+
+// RFC 6265bis §5.6 only rejects CTLs, except HTAB. The `;` can't be part of a single pair.
+#[inline]
+fn is_rfc6265bis_invalid(c: char) -> bool {
+    c != '\t' && c.is_ascii_control() || c == ';'
+}
+
+/// Returns the byte offset of `needle` in `haystack`, `needle` must be a substring of `haystack`.
+#[inline]
+pub(crate) fn offset_of(haystack: &str, needle: &str) -> usize {
+    needle.as_ptr() as usize - haystack.as_ptr() as usize
 }
 
 #[inline]
@@ -134,8 +352,8 @@ pub fn trim_quotes(value: &str) -> &str {
 }
 
 #[inline]
-pub fn invalid_token(val: &str) -> Option<char> {
-    val.chars().find(|c| match c {
+pub fn invalid_token(val: &str) -> Option<(usize, char)> {
+    val.char_indices().find(|(_, c)| match c {
         '!' | '#' | '$' | '%' | '&' | '\'' | '*' | '+' | '-' | '.' | '^' | '_' | '`' | '|'
         | '~' => false,
         c if c.is_alphanumeric() => false,
@@ -144,6 +362,115 @@ pub fn invalid_token(val: &str) -> Option<char> {
 }
 
 #[inline]
-pub fn find_invalid_cookie_value(val: &str) -> Option<char> {
-    val.chars().find(invalid_cookie_value_char)
+pub fn find_invalid_cookie_value(val: &str) -> Option<(usize, char)> {
+    val.char_indices()
+        .find(|(_, c)| invalid_cookie_value_char(c))
+}
+
+// Maps an offset into a decoded fragment back to the offset into its original bytes. Latin-1
+// decodes every byte to one char, lossy UTF-8 decoding replaces every invalid sequence with a
+// single U+FFFD.
+fn original_offset(bytes: &[u8], decoded: &str, offset: usize, non_utf8: NonUtf8) -> usize {
+    if core::str::from_utf8(bytes).is_ok() {
+        return offset;
+    }
+
+    if let NonUtf8::Latin1 = non_utf8 {
+        return decoded[..offset].chars().count();
+    }
+
+    let (mut decoded_pos, mut original_pos) = (0, 0);
+    for chunk in bytes.utf8_chunks() {
+        let valid = chunk.valid().len();
+        if offset <= decoded_pos + valid {
+            return original_pos + offset - decoded_pos;
+        }
+        decoded_pos += valid;
+        original_pos += valid;
+
+        if !chunk.invalid().is_empty() {
+            if offset < decoded_pos + char::REPLACEMENT_CHARACTER.len_utf8() {
+                return original_pos;
+            }
+            decoded_pos += char::REPLACEMENT_CHARACTER.len_utf8();
+            original_pos += chunk.invalid().len();
+        }
+    }
+
+    original_pos + offset.saturating_sub(decoded_pos)
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use alloc::{string::String, sync::Arc, vec::Vec};
+
+    use crate::{Cookie, util::RawBuf};
+
+    #[test]
+    fn header_shares_one_buffer() {
+        let cookies: Vec<Cookie> = Cookie::parse_header("a=1; b=2; c=\"3\"")
+            .map(Result::unwrap)
+            .collect();
+
+        let Some(RawBuf::Shared(first)) = &cookies[0].raw_value else {
+            panic!("cookie should point into the shared buffer");
+        };
+        for cookie in &cookies {
+            assert!(
+                matches!(&cookie.raw_value, Some(RawBuf::Shared(buf)) if Arc::ptr_eq(first, buf))
+            );
+        }
+
+        assert_eq!(cookies[2].value(), "3");
+    }
+
+    #[test]
+    fn single_cookie_keeps_its_input() {
+        let cookie = Cookie::parse_cookie(String::from("a=1; b=2")).unwrap();
+
+        assert!(matches!(cookie.raw_value, Some(RawBuf::Owned(_))));
+        assert_eq!((cookie.name(), cookie.value()), ("a", "1"));
+    }
+
+    #[test]
+    fn invalid_utf8_errors_in_header_order() {
+        use crate::{ErrorKind, NonUtf8, ParseOptions, RawCodec};
+
+        let header = b"a=\xff; b c=1; d=2";
+
+        let options = ParseOptions::new();
+        let results = Cookie::parse_header_bytes(header, &options, |_| &RawCodec);
+        let errors: Vec<_> = results
+            .iter()
+            .map(|r| r.as_ref().map_err(|e| (e.kind(), e.offset())).err())
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                Some((ErrorKind::InvalidUtf8, Some(2))),
+                Some((ErrorKind::InvalidName(' '), Some(6))),
+                None
+            ]
+        );
+
+        // Offsets after a decoded fragment point into the original bytes.
+        let header = b"a=\xff\xfe b; c=2";
+        for non_utf8 in [NonUtf8::Latin1, NonUtf8::LossyUtf8] {
+            let options = ParseOptions::new().non_utf8(non_utf8);
+            let results = Cookie::parse_header_bytes(header, &options, |_| &RawCodec);
+            let error = results[0].as_ref().unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidValue(' '));
+            assert_eq!(error.offset(), Some(4));
+            assert!(results[1].is_ok());
+        }
+    }
+
+    #[cfg(feature = "percent-encode")]
+    #[test]
+    fn decoded_cookie_drops_buffer() {
+        let cookie = Cookie::parse_cookie_encoded("a%20b=c%20d").unwrap();
+
+        assert!(cookie.raw_value.is_none());
+        assert_eq!((cookie.name(), cookie.value()), ("a b", "c d"));
+    }
 }
@@ -1,10 +1,22 @@
-use crate::{Error, cookie::parse::find_invalid_cookie_value};
+use alloc::string::String;
+
+use crate::{Error, ErrorKind, cookie::parse::find_invalid_cookie_value, error::Attribute};
 
 use super::Cookie;
 
 impl Cookie {
     #[inline]
     pub(crate) fn serialize_path(&self, buf: &mut String) -> crate::Result<()> {
+        self.validate_path()?;
+
+        if let Some(path) = self.path() {
+            buf.push_str("; Path=");
+            buf.push_str(path);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_path(&self) -> crate::Result<()> {
         let Some(path) = self.path() else {
             return Ok(());
         };
@@ -12,17 +24,19 @@ impl Cookie {
         // We're more conservative here because a path attribute makes a cookie __more secure__.
         // Simply ignore the attribute could lead to some unexpected results.
         if path.is_empty() {
-            return Err(Error::EmptyPathValue);
+            return Err(Error::serialize(ErrorKind::EmptyPathValue, Attribute::Path));
         }
 
         if !path.starts_with('/') {
-            return Err(Error::NoLeadingSlash);
-        } else if let Some(invalid_char) = find_invalid_cookie_value(path) {
-            return Err(Error::InvalidPathValue(invalid_char));
+            return Err(Error::serialize(ErrorKind::NoLeadingSlash, Attribute::Path).with_offset(0));
+        } else if let Some((i, invalid_char)) = find_invalid_cookie_value(path) {
+            return Err(Error::serialize(
+                ErrorKind::InvalidPathValue(invalid_char),
+                Attribute::Path,
+            )
+            .with_offset(i));
         }
 
-        buf.push_str("; Path=");
-        buf.push_str(path);
         Ok(())
     }
 }
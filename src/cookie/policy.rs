@@ -0,0 +1,198 @@
+use alloc::borrow::Cow;
+use core::time::Duration;
+
+use super::Cookie;
+use crate::{SameSite, util::TinyStr};
+
+/// Jar-wide attributes for the cookies added to a [`CookieJar`](crate::CookieJar).
+///
+/// A policy has two parts:
+/// * __Defaults__ are applied to the attributes that were never set on a cookie. An attribute
+///   that was explicitly set, even to `false`, is left untouched.
+/// * __Requirements__ are enforced on every cookie, overriding what was set on the cookie.
+///
+/// The policy is applied when a cookie is added to or removed from the jar, so
+/// [`CookieJar::get`](crate::CookieJar::get) and the `Set-Cookie` headers both see the resulting
+/// attributes. __Original__ cookies are never changed.
+///
+/// With the `axum` feature, add `CookiePolicy` as a request extension to set the policy of the
+/// extracted `CookieJar`.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{Cookie, CookieJar, CookiePolicy, SameSite};
+///
+/// let policy = CookiePolicy::new()
+///     .path("/")
+///     .http_only(true)
+///     .same_site(SameSite::Lax)
+///     .require_secure();
+///
+/// let mut jar = CookieJar::new();
+/// jar.set_policy(policy);
+///
+/// jar.add(Cookie::new("session", "abc"));
+/// jar.add(Cookie::build("theme", "dark").set_http_only(false));
+///
+/// let mut headers = jar.set_cookie_headers();
+/// assert_eq!(
+///     headers.next().unwrap().as_deref(),
+///     Ok("session=abc; Path=/; Secure; HttpOnly; SameSite=Lax")
+/// );
+/// assert_eq!(
+///     headers.next().unwrap().as_deref(),
+///     Ok("theme=dark; Path=/; Secure; SameSite=Lax")
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CookiePolicy {
+    max_age: Option<i64>,
+    domain: Option<Cow<'static, str>>,
+    path: Option<Cow<'static, str>>,
+    secure: Option<bool>,
+    http_only: Option<bool>,
+    partitioned: Option<bool>,
+    same_site: Option<SameSite>,
+    require_secure: bool,
+    require_http_only: bool,
+    min_same_site: Option<SameSite>,
+}
+
+impl CookiePolicy {
+    /// Creates a policy without any defaults or requirements.
+    pub const fn new() -> Self {
+        Self {
+            max_age: None,
+            domain: None,
+            path: None,
+            secure: None,
+            http_only: None,
+            partitioned: None,
+            same_site: None,
+            require_secure: false,
+            require_http_only: false,
+            min_same_site: None,
+        }
+    }
+
+    /// Sets the default Max-Age attribute in seconds.
+    pub const fn max_age_secs(mut self, max_age_secs: i64) -> Self {
+        self.max_age = Some(max_age_secs);
+        self
+    }
+
+    /// Sets the default Max-Age attribute.
+    pub const fn max_age(self, max_age: Duration) -> Self {
+        self.max_age_secs(super::MaxAge::secs_of(max_age))
+    }
+
+    /// Sets the default Domain attribute.
+    pub fn domain<D: Into<Cow<'static, str>>>(mut self, domain: D) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Sets the default Path attribute.
+    pub fn path<P: Into<Cow<'static, str>>>(mut self, path: P) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the default Secure attribute.
+    pub const fn secure(mut self, secure: bool) -> Self {
+        self.secure = Some(secure);
+        self
+    }
+
+    /// Sets the default HttpOnly attribute.
+    pub const fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = Some(http_only);
+        self
+    }
+
+    /// Sets the default Partitioned attribute.
+    pub const fn partitioned(mut self, partitioned: bool) -> Self {
+        self.partitioned = Some(partitioned);
+        self
+    }
+
+    /// Sets the default SameSite attribute.
+    pub const fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// Sets the Secure attribute on every cookie, e.g. in production.
+    pub const fn require_secure(mut self) -> Self {
+        self.require_secure = true;
+        self
+    }
+
+    /// Sets the HttpOnly attribute on every cookie.
+    pub const fn require_http_only(mut self) -> Self {
+        self.require_http_only = true;
+        self
+    }
+
+    /// Raises the SameSite attribute of every cookie to at least `same_site`, where `Strict` is
+    /// stricter than `Lax`, which is stricter than `None`. Cookies without a SameSite attribute
+    /// get `same_site`.
+    pub const fn require_same_site(mut self, same_site: SameSite) -> Self {
+        self.min_same_site = Some(same_site);
+        self
+    }
+
+    /// Applies the defaults and requirements of this policy to the cookie.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookiePolicy, SameSite};
+    ///
+    /// let policy = CookiePolicy::new().require_same_site(SameSite::Lax);
+    ///
+    /// let mut cookie = Cookie::build("session", "abc").same_site(SameSite::None).build();
+    /// policy.apply(&mut cookie);
+    /// assert_eq!(cookie.same_site(), Some(SameSite::Lax));
+    /// ```
+    pub fn apply(&self, cookie: &mut Cookie) {
+        if cookie.max_age.is_none() {
+            cookie.max_age = self.max_age;
+        }
+
+        if cookie.domain.is_none() {
+            cookie.domain = self.domain.clone().map(TinyStr::from);
+        }
+
+        if cookie.path.is_none() {
+            cookie.path = self.path.clone().map(TinyStr::from);
+        }
+
+        cookie.secure = cookie.secure.or(self.secure);
+        cookie.http_only = cookie.http_only.or(self.http_only);
+        cookie.partitioned = cookie.partitioned.or(self.partitioned);
+        cookie.same_site = cookie.same_site.or(self.same_site);
+
+        if self.require_secure {
+            cookie.secure = Some(true);
+        }
+
+        if self.require_http_only {
+            cookie.http_only = Some(true);
+        }
+
+        if let Some(min) = self.min_same_site {
+            match cookie.same_site {
+                Some(same_site) if strictness(same_site) >= strictness(min) => {}
+                _ => cookie.same_site = Some(min),
+            }
+        }
+    }
+}
+
+fn strictness(same_site: SameSite) -> u8 {
+    match same_site {
+        SameSite::None => 0,
+        SameSite::Lax => 1,
+        SameSite::Strict => 2,
+    }
+}
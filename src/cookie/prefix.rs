@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use super::{Cookie, CookieBuilder};
 
@@ -50,6 +50,21 @@ pub(crate) fn split_prefix(name: Cow<'_, str>) -> (Option<CookiePrefix>, Cow<'_,
 }
 
 impl Cookie {
+    // Sets the attributes the name prefix requires: Secure, plus Path=/ and no Domain for
+    // `__Host-`. Parsed prefixed cookies don't carry these attributes.
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_prefix_requirements(&mut self) {
+        match self.prefix {
+            Some(CookiePrefix::Host) => {
+                self.set_secure(true);
+                self.set_path("/");
+                self.unset_domain();
+            }
+            Some(CookiePrefix::Secure) => self.set_secure(true),
+            None => {}
+        }
+    }
+
     /// Builds a `__Host-` prefixed cookie.
     ///
     /// The `Secure` attribute is set and the `Path` attribute is set to `/`, and the `__Host-`
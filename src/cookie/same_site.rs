@@ -1,4 +1,5 @@
-use std::fmt::{self, Write};
+use alloc::string::String;
+use core::fmt::{self, Write};
 
 use crate::Cookie;
 
@@ -1,10 +1,12 @@
 use crate::{
-    Error, SameSite,
-    cookie::parse::{find_invalid_cookie_value, invalid_token, trim_quotes},
+    CookieCodec, Error, ErrorKind, SameSite,
+    cookie::parse::{find_invalid_cookie_value, invalid_token, offset_of, trim_quotes},
+    error::Attribute,
 };
 
 use super::Cookie;
-use std::fmt::Write;
+use alloc::string::String;
+use core::fmt::Write;
 
 impl Cookie {
     /// Serializes the cookie. Errors when:
@@ -16,19 +18,16 @@ impl Cookie {
     ///
     /// Ignores domains with invalid cookie characters.
     pub fn serialize(&self) -> crate::Result<String> {
-        self.serialize_inner(|name, value, buf| {
-            // Unencdoded values need manual validation of the characters.
-
-            let trimmed_value = trim_quotes(value);
-
-            if let Some(invalid_char) = find_invalid_cookie_value(trimmed_value) {
-                return Err(Error::InvalidValue(invalid_char));
-            } else if let Some(token) = invalid_token(name) {
-                return Err(Error::InvalidName(token));
-            }
+        self.serialize_inner(|name, value, buf| write_name_value(name, value, self.quoted, buf))
+    }
 
-            let _ = write!(buf, "{name}={value}");
-            Ok(())
+    /// Serializes the cookie and encodes the name and value with the given [`CookieCodec`].
+    /// Errors for the same reasons as [`Cookie::serialize`], the encoded name and value are
+    /// validated.
+    pub fn serialize_with(&self, codec: &dyn CookieCodec) -> crate::Result<String> {
+        self.serialize_inner(|name, value, buf| {
+            let (name, value) = (codec.encode_name(name), codec.encode_value(value));
+            write_name_value(&name, &value, self.quoted, buf)
         })
     }
 
@@ -45,11 +44,46 @@ impl Cookie {
 
         self.serialize_inner(|name, value, buf| {
             // Encoded values don't require validation since the invalid characters are encoded.
-            let _ = write!(buf, "{}={}", encode_name(name), encode_value(value));
+            let quote = if self.quoted { "\"" } else { "" };
+            let _ = write!(
+                buf,
+                "{}={quote}{}{quote}",
+                encode_name(name),
+                encode_value(value)
+            );
             Ok(())
         })
     }
 
+    // Writes the encoded `name=value` pair as a user-agent sends it in the `Cookie` header,
+    // including the name prefix.
+    #[cfg(feature = "test-util")]
+    pub(crate) fn write_cookie_pair(&self, codec: &dyn CookieCodec, buf: &mut String) {
+        let prefix = self.prefix.map(|p| p.as_str()).unwrap_or_default();
+        let quote = if self.is_quoted() { "\"" } else { "" };
+        let _ = write!(
+            buf,
+            "{prefix}{}={quote}{}{quote}",
+            codec.encode_name(self.name()),
+            codec.encode_value(self.value())
+        );
+    }
+
+    // Validates the cookie without serializing it, the name and value are validated after
+    // encoding them with the codec.
+    pub(crate) fn validate_with(&self, codec: &dyn CookieCodec) -> crate::Result<()> {
+        let name = self.name();
+        if name.is_empty() {
+            return Err(Error::serialize(ErrorKind::NameEmpty, Attribute::Name));
+        }
+
+        let (name, value) = (codec.encode_name(name), codec.encode_value(self.value()));
+        validate_name_value(&name, &value, self.quoted)?;
+
+        self.validate_path()?;
+        self.validate_domain()
+    }
+
     fn serialize_inner(
         &self,
         callback: impl Fn(&str, &str, &mut String) -> crate::Result<()>,
@@ -60,7 +94,7 @@ impl Cookie {
         let path = self.path();
 
         if name.is_empty() {
-            return Err(Error::NameEmpty);
+            return Err(Error::serialize(ErrorKind::NameEmpty, Attribute::Name));
         }
 
         let prefix = self.prefix.map(|p| p.as_str()).unwrap_or_default();
@@ -80,7 +114,7 @@ impl Cookie {
 
         // Write name and value
         // Validation happens in the callback.
-        callback(name, value, &mut buf)?;
+        callback(name, value, &mut buf).map_err(|e| e.with_name(name))?;
 
         // Expires
         if let Some(max_age) = self.max_age_secs() {
@@ -90,10 +124,12 @@ impl Cookie {
 
         self.serialize_domain(&mut buf);
 
-        self.serialize_path(&mut buf)?;
+        self.serialize_path(&mut buf)
+            .map_err(|e| e.with_name(name))?;
 
         // SameSite=None and Partitioned cookies need the Secure attribute
-        if self.is_secure() || self.is_partitioned() || self.same_site() == Some(SameSite::None) {
+        let needs_secure = self.is_partitioned() || self.same_site() == Some(SameSite::None);
+        if self.is_secure() || (needs_secure && !self.insecure) {
             buf.push_str("; Secure");
         }
 
@@ -107,7 +143,39 @@ impl Cookie {
 
         self.serialize_same_site(&mut buf);
 
-        self.serialize_expire(&mut buf)?;
+        self.serialize_expire(&mut buf)
+            .map_err(|e| e.with_name(name))?;
         Ok(buf)
     }
 }
+
+// Validates and writes the name and value.
+fn write_name_value(name: &str, value: &str, quoted: bool, buf: &mut String) -> crate::Result<()> {
+    validate_name_value(name, value, quoted)?;
+
+    if quoted {
+        let _ = write!(buf, "{name}=\"{value}\"");
+    } else {
+        let _ = write!(buf, "{name}={value}");
+    }
+    Ok(())
+}
+
+// Unencoded values need manual validation of the characters. Quoted values are validated as is,
+// otherwise optional double quotes are allowed.
+fn validate_name_value(name: &str, value: &str, quoted: bool) -> crate::Result<()> {
+    let trimmed_value = if quoted { value } else { trim_quotes(value) };
+
+    if let Some((i, invalid_char)) = find_invalid_cookie_value(trimmed_value) {
+        return Err(
+            Error::serialize(ErrorKind::InvalidValue(invalid_char), Attribute::Value)
+                .with_offset(offset_of(value, trimmed_value) + i),
+        );
+    } else if let Some((i, token)) = invalid_token(name) {
+        return Err(
+            Error::serialize(ErrorKind::InvalidName(token), Attribute::Name).with_offset(i),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use alloc::borrow::Cow;
+use core::time::Duration;
+
+use super::{Cookie, prefix::CookiePrefix};
+use crate::{CookieCategory, SameSite, util::TinyStr};
+
+/// A `const` cookie definition: a name and attributes, without a value. Use
+/// [`CookieTemplate::with_value`] to stamp out cookies at runtime.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{CookieTemplate, SameSite};
+///
+/// static SESSION: CookieTemplate = CookieTemplate::new("session")
+///     .path("/")
+///     .http_only()
+///     .secure()
+///     .same_site(SameSite::Lax);
+///
+/// let cookie = SESSION.with_value("abc");
+/// assert_eq!(
+///     cookie.serialize().as_deref(),
+///     Ok("session=abc; Path=/; Secure; HttpOnly; SameSite=Lax")
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CookieTemplate {
+    name: &'static str,
+    max_age: Option<i64>,
+    domain: Option<&'static str>,
+    path: Option<&'static str>,
+    secure: bool,
+    http_only: bool,
+    partitioned: bool,
+    same_site: Option<SameSite>,
+    prefix: Option<CookiePrefix>,
+    quoted: bool,
+    category: CookieCategory,
+}
+
+impl CookieTemplate {
+    /// Creates a template for cookies with the given name and no attributes.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            max_age: None,
+            domain: None,
+            path: None,
+            secure: false,
+            http_only: false,
+            partitioned: false,
+            same_site: None,
+            prefix: None,
+            quoted: false,
+            category: CookieCategory::Necessary,
+        }
+    }
+
+    /// Creates a template for `__Host-` prefixed cookies, see [`Cookie::host`].
+    pub const fn host(name: &'static str) -> Self {
+        let mut template = Self::new(name).secure().path("/");
+        template.prefix = Some(CookiePrefix::Host);
+        template
+    }
+
+    /// Creates a template for `__Secure-` prefixed cookies, see [`Cookie::secure`].
+    pub const fn secure_prefixed(name: &'static str) -> Self {
+        let mut template = Self::new(name).secure();
+        template.prefix = Some(CookiePrefix::Secure);
+        template
+    }
+
+    /// Returns the name of the cookies created from this template.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Sets the Max-Age attribute in seconds.
+    pub const fn max_age_secs(mut self, max_age_secs: i64) -> Self {
+        self.max_age = Some(max_age_secs);
+        self
+    }
+
+    /// Sets the Max-Age attribute.
+    pub const fn max_age(self, max_age: Duration) -> Self {
+        self.max_age_secs(super::MaxAge::secs_of(max_age))
+    }
+
+    /// Sets the Domain attribute.
+    pub const fn domain(mut self, domain: &'static str) -> Self {
+        self.domain = Some(domain);
+        self
+    }
+
+    /// Sets the Path attribute.
+    pub const fn path(mut self, path: &'static str) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    /// Sets the Secure attribute.
+    pub const fn secure(mut self) -> Self {
+        self.secure = true;
+        self
+    }
+
+    /// Sets the HttpOnly attribute.
+    pub const fn http_only(mut self) -> Self {
+        self.http_only = true;
+        self
+    }
+
+    /// Sets the Partitioned attribute, this also enables the Secure attribute while serializing.
+    pub const fn partitioned(mut self) -> Self {
+        self.partitioned = true;
+        self
+    }
+
+    /// Sets the SameSite attribute.
+    pub const fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// Surrounds the value with double quotes when serializing, see [`Cookie::set_quoted`].
+    pub const fn quoted(mut self) -> Self {
+        self.quoted = true;
+        self
+    }
+
+    /// Sets the consent category, see [`CookieCategory`].
+    pub const fn category(mut self, category: CookieCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Creates a cookie with the given value and the attributes of this template.
+    pub fn with_value<V: Into<Cow<'static, str>>>(&self, value: V) -> Cookie {
+        let mut cookie = Cookie::new_inner(TinyStr::Static(self.name), TinyStr::from(value));
+
+        cookie.max_age = self.max_age;
+        cookie.domain = self.domain.map(TinyStr::Static);
+        cookie.path = self.path.map(TinyStr::Static);
+        cookie.secure = self.secure.then_some(true);
+        cookie.http_only = self.http_only.then_some(true);
+        cookie.partitioned = self.partitioned.then_some(true);
+        cookie.same_site = self.same_site;
+        cookie.prefix = self.prefix;
+        cookie.quoted = self.quoted;
+        cookie.category = self.category;
+        cookie
+    }
+
+    /// Creates a cookie that removes the cookie created from this template from the user-agent,
+    /// with the same Domain and Path attributes. See [`Cookie::remove`].
+    ///
+    /// The Expires attribute is based on the system clock, use `CookieTemplate::remove_with` to
+    /// use a `Clock` instead.
+    pub fn remove(&self) -> Cookie {
+        self.with_value("").into_remove(super::Expires::remove())
+    }
+
+    /// Creates a cookie that removes the cookie created from this template from the user-agent,
+    /// like [`CookieTemplate::remove`]. The Expires attribute is set to one year before the
+    /// current time of `clock`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{CookieTemplate, FixedClock};
+    ///
+    /// static SESSION: CookieTemplate = CookieTemplate::new("session").path("/");
+    ///
+    /// let clock = FixedClock::from_unix_timestamp(784_111_777);
+    /// assert_eq!(
+    ///     SESSION.remove_with(&clock).serialize().as_deref(),
+    ///     Ok("session=; Max-Age=0; Path=/; Expires=Sat, 06 Nov 1993 08:49:37 GMT")
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn remove_with(&self, clock: &dyn crate::Clock) -> Cookie {
+        self.with_value("")
+            .into_remove(super::Expires::remove_with(clock))
+    }
+}
+
+impl From<CookieTemplate> for Cookie {
+    fn from(template: CookieTemplate) -> Self {
+        template.with_value("")
+    }
+}
@@ -1,9 +1,34 @@
-use std::fmt::Display;
+use alloc::boxed::Box;
+use core::fmt::{self, Display};
+
+/// An error that can be returned while parsing or serializing cookies.
+///
+/// Besides the [`ErrorKind`], the error carries the context it occurred in: the [`Phase`] that
+/// failed, the name of the cookie if it's known and the byte offset into the input.
+///
+/// # Example
+/// ```rust
+/// use cookie_monster::{Cookie, ErrorKind, Phase};
+///
+/// let error = Cookie::parse_cookie("session=a\0b").unwrap_err();
+///
+/// assert_eq!(error.kind(), ErrorKind::InvalidValue('\0'));
+/// assert_eq!(error.phase(), Phase::Parse);
+/// assert_eq!(error.name(), Some("session"));
+/// assert_eq!(error.offset(), Some(9));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    phase: Phase,
+    name: Option<Box<str>>,
+    offset: Option<usize>,
+}
 
 /// All errors that can be returned while parsing or serializing cookies.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
-pub enum Error {
+pub enum ErrorKind {
     /// No '=' found in the cookie string.
     EqualsNotFound,
     /// Name value is empty.
@@ -12,12 +37,23 @@ pub enum Error {
     InvalidName(char),
     /// Value contains invalid character.
     InvalidValue(char),
+    /// The cookie is not valid UTF-8.
+    InvalidUtf8,
 
     /// Unable to format the expires field.
     ExpiresFmt,
+    /// The date is not a valid IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+    InvalidDate,
 
     /// Could not percent-decode the cookie.
     PercentDecodeError,
+    /// Could not base64url-decode the cookie value.
+    Base64DecodeError,
+
+    /// Could not decompress the cookie value.
+    DecompressError,
+    /// The cookie value decompresses to more than the configured maximum size.
+    DecompressedTooLarge,
 
     /// Path attribute contains an invalid character.
     InvalidPathValue(char),
@@ -25,30 +61,189 @@ pub enum Error {
     EmptyPathValue,
     /// Path does not start with a leading '/'.
     NoLeadingSlash,
+
+    /// Domain attribute contains an invalid character.
+    InvalidDomainValue(char),
+
+    /// The serialized cookie is not a valid header value.
+    InvalidHeaderValue,
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// The phase in which an [`Error`] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Phase {
+    /// Parsing a `Cookie` header value.
+    Parse,
+    /// Decoding a cookie name or value, e.g. percent-decoding or decompressing.
+    Decode,
+    /// Serializing the given part of the cookie.
+    Serialize(Attribute),
+    /// Writing a serialized cookie to a header.
+    Write,
+}
+
+/// The part of a cookie that failed to serialize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Attribute {
+    /// The cookie name.
+    Name,
+    /// The cookie value.
+    Value,
+    /// The Expires attribute.
+    Expires,
+    /// The Path attribute.
+    Path,
+    /// The Domain attribute.
+    Domain,
+}
+
+impl Error {
+    pub(crate) fn new(kind: ErrorKind, phase: Phase) -> Self {
+        Self {
+            kind,
+            phase,
+            name: None,
+            offset: None,
+        }
+    }
+
+    pub(crate) fn parse(kind: ErrorKind) -> Self {
+        Self::new(kind, Phase::Parse)
+    }
+
+    pub(crate) fn decode(kind: ErrorKind) -> Self {
+        Self::new(kind, Phase::Decode)
+    }
+
+    pub(crate) fn serialize(kind: ErrorKind, attribute: Attribute) -> Self {
+        Self::new(kind, Phase::Serialize(attribute))
+    }
+
+    pub(crate) fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub(crate) fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    // Moves the offset, used when the input was part of a larger input.
+    pub(crate) fn shift_offset(mut self, by: usize) -> Self {
+        self.offset = self.offset.map(|offset| offset + by);
+        self
+    }
+
+    /// Returns what went wrong.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the phase in which the error occurred.
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Returns the name of the cookie, if it's known.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the byte offset into the input where the error occurred, if it's known.
+    ///
+    /// While parsing, this is the offset into the parsed string, e.g. the whole `Cookie` header.
+    /// While serializing, this is the offset into the attribute that failed.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl From<Error> for ErrorKind {
+    fn from(error: Error) -> Self {
+        error.kind
+    }
+}
+
+impl PartialEq<ErrorKind> for Error {
+    fn eq(&self, other: &ErrorKind) -> bool {
+        self.kind == *other
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let err = match self {
-            Error::EqualsNotFound => "No '=' found in the cookie",
-            Error::NameEmpty => "The cookie name is empty",
-            Error::InvalidName(c) => {
+            ErrorKind::EqualsNotFound => "No '=' found in the cookie",
+            ErrorKind::NameEmpty => "The cookie name is empty",
+            ErrorKind::InvalidName(c) => {
                 return write!(f, "The cookie name contains an invalid character: {c}");
             }
-            Error::InvalidValue(c) => {
+            ErrorKind::InvalidValue(c) => {
                 return write!(f, "The cookie value contains an invalid character: {c}");
             }
-            Error::ExpiresFmt => "Failed to format the expires value",
-            Error::PercentDecodeError => "An error occurred while decoding",
-            Error::InvalidPathValue(c) => {
+            ErrorKind::InvalidUtf8 => "The cookie is not valid UTF-8",
+            ErrorKind::ExpiresFmt => "Failed to format the expires value",
+            ErrorKind::InvalidDate => "The date is not a valid IMF-fixdate",
+            ErrorKind::PercentDecodeError => "An error occurred while decoding",
+            ErrorKind::Base64DecodeError => "The cookie value is not valid base64url",
+            ErrorKind::DecompressError => "An error occurred while decompressing",
+            ErrorKind::DecompressedTooLarge => "The decompressed cookie value is too large",
+            ErrorKind::InvalidPathValue(c) => {
                 return write!(f, "The path attribute contains an invalid character ({c})");
             }
-            Error::EmptyPathValue => "The path attribute is empty",
-            Error::NoLeadingSlash => "The path attribute does not start with a leading slash",
+            ErrorKind::EmptyPathValue => "The path attribute is empty",
+            ErrorKind::NoLeadingSlash => "The path attribute does not start with a leading slash",
+            ErrorKind::InvalidDomainValue(c) => {
+                return write!(
+                    f,
+                    "The domain attribute contains an invalid character ({c})"
+                );
+            }
+            ErrorKind::InvalidHeaderValue => "The cookie is not a valid header value",
         };
 
         f.write_str(err)
     }
 }
 
-impl std::error::Error for Error {}
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => f.write_str("parsing"),
+            Phase::Decode => f.write_str("decoding"),
+            Phase::Serialize(Attribute::Name) => f.write_str("serializing the name"),
+            Phase::Serialize(Attribute::Value) => f.write_str("serializing the value"),
+            Phase::Serialize(Attribute::Expires) => {
+                f.write_str("serializing the Expires attribute")
+            }
+            Phase::Serialize(Attribute::Path) => f.write_str("serializing the Path attribute"),
+            Phase::Serialize(Attribute::Domain) => f.write_str("serializing the Domain attribute"),
+            Phase::Write => f.write_str("writing the header"),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} while {}", self.kind, self.phase)?;
+
+        if let Some(name) = &self.name {
+            match self.phase {
+                Phase::Parse | Phase::Decode => write!(f, " cookie `{name}`")?,
+                Phase::Serialize(_) => write!(f, " of cookie `{name}`")?,
+                Phase::Write => write!(f, " for cookie `{name}`")?,
+            }
+        }
+
+        if let Some(offset) = self.offset {
+            write!(f, " at byte {offset}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for Error {}
@@ -1,6 +1,15 @@
-use http::HeaderMap;
+use core::fmt::Display;
+use std::{string::String, vec::Vec};
 
-use crate::{Cookie, CookieJar};
+use http::{
+    HeaderMap, HeaderValue, Uri,
+    header::{HOST, SET_COOKIE},
+};
+
+use crate::{
+    Cookie, CookieCodec, CookieCodecs, CookieJar, Error, ErrorKind, ParseOptions, PercentCodec,
+    Phase, RawCodec,
+};
 
 impl CookieJar {
     /// Builds a `CookieJar` from the `Cookie` request headers, percent-decoding
@@ -8,26 +17,252 @@ impl CookieJar {
     ///
     /// Duplicate cookie names resolve to the **last** occurrence; see
     /// [`from_cookie`](CookieJar::from_cookie) for the cookie-shadowing note.
+    ///
+    /// The headers are parsed byte by byte, a cookie that is not valid UTF-8 is skipped without
+    /// affecting the other cookies. Use
+    /// [`from_headers_with_options`](CookieJar::from_headers_with_options) to decode these
+    /// cookies instead.
     pub fn from_headers(headers: &HeaderMap) -> Self {
-        let iter = headers
-            .get_all("cookie")
-            .into_iter()
-            .filter_map(|header| header.to_str().ok())
-            .flat_map(|cookie_str| cookie_str.split(';'))
-            .filter_map(|string| Cookie::parse_cookie_encoded(string).ok());
+        Self::from_headers_with_options(headers, &ParseOptions::new())
+    }
+
+    /// Builds a `CookieJar` from the `Cookie` request headers with the given options,
+    /// percent-decoding names and values and ignoring cookies that fail to parse.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar, NonUtf8, ParseOptions};
+    /// use http::{HeaderMap, HeaderValue};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.append(
+    ///     "cookie",
+    ///     HeaderValue::from_bytes(b"session=abc; tracker=caf\xe9").unwrap(),
+    /// );
+    ///
+    /// let jar = CookieJar::from_headers(&headers);
+    /// assert_eq!(jar.get("session").map(Cookie::value), Some("abc"));
+    /// assert!(jar.get("tracker").is_none());
+    ///
+    /// let options = ParseOptions::new().non_utf8(NonUtf8::Latin1);
+    /// let jar = CookieJar::from_headers_with_options(&headers, &options);
+    /// assert_eq!(jar.get("tracker").map(Cookie::value), Some("café"));
+    /// ```
+    pub fn from_headers_with_options(headers: &HeaderMap, options: &ParseOptions) -> Self {
+        Self::from_headers_inner(headers, options, None)
+    }
+
+    /// Builds a `CookieJar` from the `Cookie` request headers without decoding the names and
+    /// values, ignoring cookies that fail to parse. Use this for cookies that contain a literal
+    /// `%` that isn't a valid escape.
+    ///
+    /// Duplicate cookie names resolve to the **last** occurrence; see
+    /// [`from_cookie`](CookieJar::from_cookie) for the cookie-shadowing note.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar};
+    /// use http::{HeaderMap, HeaderValue};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.append("cookie", HeaderValue::from_static("legacy=a%FF"));
+    ///
+    /// assert!(CookieJar::from_headers(&headers).get("legacy").is_none());
+    ///
+    /// let jar = CookieJar::from_headers_raw(&headers);
+    /// assert_eq!(jar.get("legacy").map(Cookie::value), Some("a%FF"));
+    /// ```
+    pub fn from_headers_raw(headers: &HeaderMap) -> Self {
+        CookieJar::from_original(parse_headers(headers, &ParseOptions::new(), |_| &RawCodec))
+    }
+
+    /// Builds a `CookieJar` from the `Cookie` request headers, decoding every cookie with the
+    /// codec that `codecs` selects for its name and ignoring cookies that fail to parse.
+    ///
+    /// The codecs are also used to encode the changes when the jar is written, see
+    /// [`set_codecs`](CookieJar::set_codecs).
+    pub fn from_headers_with(headers: &HeaderMap, codecs: CookieCodecs) -> Self {
+        Self::from_headers_inner(headers, &ParseOptions::new(), Some(codecs))
+    }
+
+    pub(crate) fn from_headers_inner(
+        headers: &HeaderMap,
+        options: &ParseOptions,
+        codecs: Option<CookieCodecs>,
+    ) -> Self {
+        let Some(codecs) = codecs else {
+            return CookieJar::from_original(parse_headers(headers, options, |_| &PercentCodec));
+        };
 
-        CookieJar::from_original(iter)
+        let mut jar =
+            CookieJar::from_original(parse_headers(headers, options, |name| codecs.get(name)));
+        jar.set_codecs(codecs);
+        jar
     }
 
+    /// Enables dev mode if the request was made to a loopback host over plain HTTP, see
+    /// [`enable_dev_mode`](CookieJar::enable_dev_mode). Returns if dev mode is enabled.
+    ///
+    /// The host is taken from the `Host` header, or from the request URI for HTTP/2 requests.
+    /// The request counts as HTTPS if the URI has the `https` scheme or the
+    /// `X-Forwarded-Proto` header is `https`.
+    ///
+    /// **These headers are controlled by the client or a reverse proxy, see the warning on
+    /// [`enable_dev_mode`](CookieJar::enable_dev_mode). Only use this if the application is not
+    /// reachable through a proxy.**
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::CookieJar;
+    /// use http::Request;
+    ///
+    /// let request = Request::get("/").header("host", "127.0.0.1:8080").body(()).unwrap();
+    ///
+    /// let mut jar = CookieJar::new();
+    /// assert!(jar.enable_dev_mode_for_request(request.uri(), request.headers()));
+    /// ```
+    pub fn enable_dev_mode_for_request(&mut self, uri: &Uri, headers: &HeaderMap) -> bool {
+        let host = headers
+            .get(HOST)
+            .and_then(|host| host.to_str().ok())
+            .or_else(|| uri.authority().map(|authority| authority.as_str()));
+
+        let https = uri.scheme_str() == Some("https")
+            || headers
+                .get("x-forwarded-proto")
+                .is_some_and(|proto| proto.as_bytes().eq_ignore_ascii_case(b"https"));
+
+        match host {
+            Some(host) => self.enable_dev_mode(host, https),
+            None => false,
+        }
+    }
+
+    /// Encodes all changes and appends them as `Set-Cookie` headers. The cookies are
+    /// percent-encoded, unless other codecs are set with [`set_codecs`](CookieJar::set_codecs).
+    /// Cookies that fail to serialize are ignored, use
+    /// [`try_write_cookies`](CookieJar::try_write_cookies) to find out which cookies were not
+    /// written.
     pub fn write_cookies(self, headers: &mut HeaderMap) {
+        let _ = self.try_write_cookies(headers);
+    }
+
+    /// Appends all changes as `Set-Cookie` headers without encoding them, even if codecs are set
+    /// with [`set_codecs`](CookieJar::set_codecs). Cookies that fail to serialize, for example
+    /// because the value contains a space, are ignored.
+    pub fn write_cookies_raw(self, headers: &mut HeaderMap) {
+        let _ = self.write_with(headers, |_| &RawCodec);
+    }
+
+    /// Encodes all changes and appends them as `Set-Cookie` headers. The cookies are
+    /// percent-encoded, unless other codecs are set with [`set_codecs`](CookieJar::set_codecs).
+    ///
+    /// All cookies that could be serialized are written, even if some failed. The cookies that
+    /// failed are returned together with the reason they failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar, ErrorKind};
+    /// use http::HeaderMap;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add(Cookie::new("theme", "dark"));
+    /// jar.add(Cookie::build("session", "abc").path("no-leading-slash"));
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// let errors = jar.try_write_cookies(&mut headers).unwrap_err();
+    ///
+    /// assert_eq!(headers.get_all("set-cookie").iter().count(), 1);
+    /// assert_eq!(errors[0].name(), "session");
+    /// assert_eq!(errors[0].error().kind(), ErrorKind::NoLeadingSlash);
+    /// ```
+    pub fn try_write_cookies(&self, headers: &mut HeaderMap) -> Result<(), Vec<SetCookieError>> {
+        self.write_with(headers, |name| self.codec_for(name, &PercentCodec))
+    }
+
+    fn write_with<'c>(
+        &self,
+        headers: &mut HeaderMap,
+        codec_for: impl Fn(&str) -> &'c dyn CookieCodec,
+    ) -> Result<(), Vec<SetCookieError>> {
+        let mut errors = Vec::new();
+
         for cookie in self.iter_non_original() {
-            if let Some(header) = cookie
-                .serialize_encoded()
-                .ok()
-                .and_then(|string| string.parse().ok())
-            {
-                headers.append("set-cookie", header);
+            let codec = codec_for(cookie.name());
+
+            match cookie.to_header_value(codec) {
+                Ok(header) => {
+                    headers.append(SET_COOKIE, header);
+                }
+                Err(error) => errors.push(SetCookieError::new(cookie.name(), error)),
             }
         }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+// Parses all `Cookie` headers as bytes, so a cookie that is not valid UTF-8 doesn't affect the
+// other cookies.
+fn parse_headers<'c>(
+    headers: &HeaderMap,
+    options: &ParseOptions,
+    codec_for: impl Copy + Fn(&str) -> &'c dyn CookieCodec,
+) -> impl Iterator<Item = Cookie> {
+    headers
+        .get_all("cookie")
+        .into_iter()
+        .flat_map(move |header| Cookie::parse_header_bytes(header.as_bytes(), options, codec_for))
+        .filter_map(Result::ok)
+}
+
+impl Cookie {
+    pub(crate) fn to_header_value(&self, codec: &dyn CookieCodec) -> crate::Result<HeaderValue> {
+        let string = self.serialize_with(codec)?;
+        HeaderValue::try_from(string).map_err(|_| {
+            Error::new(ErrorKind::InvalidHeaderValue, Phase::Write).with_name(self.name())
+        })
+    }
+}
+
+/// An error that occurred while writing a cookie to a `Set-Cookie` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCookieError {
+    name: String,
+    error: Error,
+}
+
+impl SetCookieError {
+    pub(crate) fn new(name: &str, error: Error) -> Self {
+        Self {
+            name: name.into(),
+            error,
+        }
+    }
+
+    /// Returns the name of the cookie that could not be written.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the reason the cookie could not be written.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+impl Display for SetCookieError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Failed to set cookie `{}`: {}", self.name, self.error)
+    }
+}
+
+impl core::error::Error for SetCookieError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
     }
 }
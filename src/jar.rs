@@ -1,6 +1,22 @@
-use std::{borrow::Borrow, collections::HashSet, fmt::Debug, hash::Hash};
+use alloc::{borrow::Cow, collections::BTreeSet, format, string::String, vec::Vec};
+use core::fmt::Debug;
 
-use crate::Cookie;
+use crate::{
+    Consent, Cookie, CookieCodec, CookieCodecs, CookiePolicy, Expires, ParseOptions, RawCodec,
+};
+
+#[cfg(feature = "std")]
+use {
+    crate::{Clock, MaxAge},
+    alloc::sync::Arc,
+};
+
+#[cfg(feature = "compression")]
+use crate::Compression;
+
+// The name suffix of the companion cookie that holds the expiry of a sliding session cookie.
+// Cookies with this suffix are reserved for the jar and not visible.
+const SLIDING_SUFFIX: &str = "~expires";
 
 /// A generic `CookieJar` for cookie management. Can be used to read update or delete cookies from
 /// a user session.
@@ -32,12 +48,43 @@ use crate::Cookie;
 ///     jar
 /// }
 /// ```
+///
+/// ## Ordering
+/// The jar keeps cookies in insertion order, so the `Set-Cookie` headers are always emitted in
+/// the order the changes were made.
+///
+/// Changes are tracked per cookie identity: the name, `Domain` and `Path` attributes. This means
+/// that removing a cookie and adding a replacement under a different path results in two
+/// `Set-Cookie` headers, the removal first.
+///
+/// ```rust
+/// use cookie_monster::{Cookie, CookieJar};
+///
+/// let mut jar = CookieJar::new();
+/// jar.remove(Cookie::named("session").path("/old"));
+/// jar.add(Cookie::build("session", "abc").path("/"));
+///
+/// let mut headers = jar.set_cookie_headers();
+/// assert!(headers.next().unwrap().unwrap().starts_with("session=; Max-Age=0; Path=/old"));
+/// assert_eq!(headers.next().unwrap().as_deref(), Ok("session=abc; Path=/"));
+/// ```
 #[derive(Default, Debug)]
 pub struct CookieJar {
-    cookies: HashSet<HashCookie>,
+    cookies: Vec<JarCookie>,
+    // The names of the cookies that were added or removed. Changes are only dropped all at once by
+    // `reset_delta`, or replaced by a change with the same name, so names are never unset.
+    changed: BTreeSet<String>,
+    codecs: Option<CookieCodecs>,
+    policy: Option<CookiePolicy>,
+    dev_mode: bool,
+    consent_cookie: Option<Cow<'static, str>>,
+    #[cfg(feature = "std")]
+    clock: Option<Arc<dyn Clock>>,
+    #[cfg(feature = "compression")]
+    compression: Option<Compression>,
 }
 
-pub(crate) enum HashCookie {
+pub(crate) enum JarCookie {
     // An original cookie. These should never be sent back to the user-agent.
     Original(Cookie),
     // A new cookie, the should always be sent back to the user-agent.
@@ -47,43 +94,21 @@ pub(crate) enum HashCookie {
     Removal(Cookie),
 }
 
-impl HashCookie {
-    fn name(&self) -> &str {
+impl JarCookie {
+    fn cookie(&self) -> &Cookie {
         match self {
-            HashCookie::Original(c) => c.name(),
-            HashCookie::New(c) => c.name(),
-            HashCookie::Removal(c) => c.name(),
+            JarCookie::Original(c) | JarCookie::New(c) | JarCookie::Removal(c) => c,
         }
     }
-}
-
-impl Hash for HashCookie {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.name().hash(state);
-    }
-}
 
-impl PartialEq for HashCookie {
-    fn eq(&self, other: &Self) -> bool {
-        self.name() == other.name()
+    fn is_original(&self) -> bool {
+        matches!(self, JarCookie::Original(_))
     }
 }
 
-impl Borrow<str> for HashCookie {
-    fn borrow(&self) -> &str {
-        self.name()
-    }
-}
-
-impl Eq for HashCookie {}
-
-impl Debug for HashCookie {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            HashCookie::Original(cookie) => cookie.fmt(f),
-            HashCookie::New(cookie) => cookie.fmt(f),
-            HashCookie::Removal(cookie) => cookie.fmt(f),
-        }
+impl Debug for JarCookie {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.cookie().fmt(f)
     }
 }
 
@@ -114,7 +139,7 @@ impl CookieJar {
     /// assert_eq!(jar.get("name").map(|c| c.value()), Some("second"));
     /// ```
     pub fn from_cookie(header: &str) -> Self {
-        Self::from_original(header.split(';').flat_map(Cookie::parse_cookie))
+        Self::from_original(Cookie::parse_header(header).flatten())
     }
 
     /// Parses the given `cookie` header value and return a `CookieJar`. The cookie name and values
@@ -124,16 +149,300 @@ impl CookieJar {
     /// the **last** occurrence; see its docs for the cookie-shadowing note.
     #[cfg(feature = "percent-encode")]
     pub fn from_encoded_cookie(header: &str) -> Self {
-        Self::from_original(header.split(';').flat_map(Cookie::parse_cookie_encoded))
+        Self::from_original(Cookie::parse_header_encoded(header).flatten())
+    }
+
+    /// Parses the given `cookie` header value with the given options and return a `CookieJar`.
+    /// Cookies that were not able to be parsed are ignored.
+    ///
+    /// Like [`from_cookie`](Self::from_cookie), duplicate cookie names resolve to the **last**
+    /// occurrence.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar, ParseOptions, ParseProfile};
+    ///
+    /// let header = "session=abc; tracking=a,b";
+    /// assert!(CookieJar::from_cookie(header).get("tracking").is_none());
+    ///
+    /// let options = ParseOptions::new().profile(ParseProfile::Rfc6265bis);
+    /// let jar = CookieJar::from_cookie_with_options(header, &options);
+    /// assert_eq!(jar.get("tracking").map(Cookie::value), Some("a,b"));
+    /// ```
+    pub fn from_cookie_with_options(header: &str, options: &ParseOptions) -> Self {
+        Self::from_original(Cookie::parse_header_with(header, options, |_| &RawCodec).flatten())
+    }
+
+    /// Parses the given `cookie` header value as bytes and return a `CookieJar`. Cookies that are
+    /// not valid UTF-8 are decoded or skipped according to the `options`, without affecting the
+    /// other cookies in the header. Cookies that were not able to be parsed are ignored.
+    ///
+    /// Like [`from_cookie`](Self::from_cookie), duplicate cookie names resolve to the **last**
+    /// occurrence.
+    pub fn from_cookie_bytes(header: &[u8], options: &ParseOptions) -> Self {
+        Self::from_original(
+            Cookie::parse_header_bytes(header, options, |_| &RawCodec)
+                .into_iter()
+                .flatten(),
+        )
+    }
+
+    /// Parses the given `cookie` header value and return a `CookieJar`. Every cookie is decoded with
+    /// the codec that `codecs` selects for its name, cookies that were not able to be parsed or
+    /// decoded are ignored.
+    ///
+    /// The codecs are also used to encode the changes, see [`set_codecs`](Self::set_codecs).
+    /// Like [`from_cookie`](Self::from_cookie), duplicate cookie names resolve to the **last**
+    /// occurrence.
+    pub fn from_cookie_with(header: &str, codecs: CookieCodecs) -> Self {
+        let mut jar = Self::from_original(
+            Cookie::parse_header_with(header, &ParseOptions::new(), |name| codecs.get(name))
+                .flatten(),
+        );
+        jar.codecs = Some(codecs);
+        jar
+    }
+
+    /// Sets the codecs that are used to encode the changes in this jar.
+    ///
+    /// By default, [`set_cookie_headers`](Self::set_cookie_headers) doesn't encode the cookies
+    /// and the `http` and `axum` integrations percent-encode them. __Original__ cookies that are
+    /// already in the jar are not decoded again.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Base64UrlCodec, Cookie, CookieCodecs, CookieJar, RawCodec};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.set_codecs(CookieCodecs::new(RawCodec).with("data", Base64UrlCodec));
+    /// jar.add(Cookie::new("data", "hello"));
+    ///
+    /// let mut headers = jar.set_cookie_headers();
+    /// assert_eq!(headers.next().unwrap().as_deref(), Ok("data=aGVsbG8"));
+    /// ```
+    pub fn set_codecs(&mut self, codecs: CookieCodecs) {
+        self.codecs = Some(codecs);
+    }
+
+    /// Returns the codecs set on this jar, if any.
+    pub fn codecs(&self) -> Option<&CookieCodecs> {
+        self.codecs.as_ref()
+    }
+
+    /// Sets the policy that supplies default attributes and enforces required attributes for the
+    /// cookies that are added to or removed from this jar. The policy is applied again when the
+    /// `Set-Cookie` headers are written, so it also covers changes made before it was set.
+    /// Original cookies are not changed. See [`CookiePolicy`] for an example.
+    pub fn set_policy(&mut self, policy: CookiePolicy) {
+        self.policy = Some(policy);
+    }
+
+    /// Returns the policy set on this jar, if any.
+    pub fn policy(&self) -> Option<&CookiePolicy> {
+        self.policy.as_ref()
+    }
+
+    /// Sets the name of the cookie that holds the [`Consent`] of the user.
+    ///
+    /// Once set, newly added cookies whose [category](crate::CookieCategory) is not consented to
+    /// are not sent to the user-agent. A removal is sent instead, so a cookie that was set before
+    /// the consent was withdrawn is removed from the user-agent. The cookies stay visible in the
+    /// jar. Without a consent cookie in the jar, only necessary cookies are sent.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Consent, Cookie, CookieCategory, CookieJar};
+    ///
+    /// let mut jar = CookieJar::from_cookie("consent=preferences");
+    /// jar.set_consent_cookie("consent");
+    ///
+    /// jar.add(Cookie::build("theme", "dark").category(CookieCategory::Preferences));
+    /// jar.add(Cookie::build("_ga", "GA1.1").category(CookieCategory::Analytics));
+    /// assert_eq!(jar.consent(), Some(Consent::none().with(CookieCategory::Preferences)));
+    ///
+    /// let mut headers = jar.set_cookie_headers();
+    /// assert_eq!(headers.next().unwrap().as_deref(), Ok("theme=dark"));
+    /// assert!(headers.next().unwrap().unwrap().starts_with("_ga=; Max-Age=0"));
+    /// ```
+    pub fn set_consent_cookie(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.consent_cookie = Some(name.into());
+    }
+
+    /// Returns the consent read from the [consent cookie](Self::set_consent_cookie), or `None` if
+    /// no consent cookie is set. A consent cookie that was added to the jar takes effect
+    /// immediately.
+    pub fn consent(&self) -> Option<Consent> {
+        let name = self.consent_cookie.as_deref()?;
+        Some(match self.get(name) {
+            Some(cookie) => Consent::parse(cookie.value()),
+            None => Consent::none(),
+        })
+    }
+
+    /// Enables dev mode if `host` is a loopback host and the request was made over plain HTTP.
+    /// Returns if dev mode is enabled.
+    ///
+    /// In dev mode the Secure attribute and the `__Host-` / `__Secure-` prefixes are stripped
+    /// from the `Set-Cookie` headers, also for `SameSite=None` and Partitioned cookies, because
+    /// some browsers reject Secure cookies over `http://localhost`. The `host` is the value of the
+    /// `Host` header, with an optional port. Only `localhost`, `*.localhost` and loopback IP
+    /// addresses are accepted, for any other host dev mode is not enabled.
+    ///
+    /// **The `Host` header and the scheme are controlled by the client or a reverse proxy. A proxy
+    /// that rewrites the `Host` header to `localhost` makes every production request look local,
+    /// prefer [`set_dev_mode`](Self::set_dev_mode) with a value from the configuration of the
+    /// application.**
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// assert!(!jar.enable_dev_mode("example.com", false));
+    /// assert!(!jar.enable_dev_mode("localhost:3000", true));
+    /// assert!(jar.enable_dev_mode("localhost:3000", false));
+    ///
+    /// jar.add(Cookie::host("session", "abc"));
+    ///
+    /// let mut headers = jar.set_cookie_headers();
+    /// assert_eq!(headers.next().unwrap().as_deref(), Ok("session=abc; Path=/"));
+    /// ```
+    pub fn enable_dev_mode(&mut self, host: &str, https: bool) -> bool {
+        self.dev_mode = !https && crate::util::is_loopback_host(host);
+        self.dev_mode
+    }
+
+    /// Enables or disables dev mode, regardless of the request. See
+    /// [`enable_dev_mode`](Self::enable_dev_mode) for what dev mode does.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.set_dev_mode(cfg!(debug_assertions));
+    /// ```
+    pub fn set_dev_mode(&mut self, enabled: bool) {
+        self.dev_mode = enabled;
+    }
+
+    /// Returns if dev mode is enabled, see [`enable_dev_mode`](Self::enable_dev_mode).
+    pub fn is_dev_mode(&self) -> bool {
+        self.dev_mode
+    }
+
+    /// Sets the clock that is used to create removal cookies, see
+    /// [`Expires::remove_with`](crate::Expires::remove_with). Defaults to the
+    /// [`SystemClock`](crate::SystemClock). See [`FixedClock`](crate::FixedClock) for an example.
+    #[cfg(feature = "std")]
+    pub fn set_clock(&mut self, clock: impl Clock) {
+        self.clock = Some(Arc::new(clock));
+    }
+
+    /// Returns the clock of this jar.
+    #[cfg(feature = "std")]
+    pub fn clock(&self) -> &dyn Clock {
+        match &self.clock {
+            Some(clock) => clock.as_ref(),
+            None => &crate::SystemClock,
+        }
+    }
+
+    // The Expires attribute of removal cookies.
+    fn removal_expires(&self) -> Expires {
+        #[cfg(feature = "std")]
+        if let Some(clock) = &self.clock {
+            return Expires::remove_with(clock.as_ref());
+        }
+
+        Expires::remove()
+    }
+
+    fn apply_policy(&self, mut cookie: Cookie) -> Cookie {
+        if let Some(policy) = &self.policy {
+            policy.apply(&mut cookie);
+        }
+        cookie
+    }
+
+    // Returns the codec that should be used to encode the cookie with the given name.
+    pub(crate) fn codec_for<'a>(
+        &'a self,
+        name: &str,
+        fallback: &'a dyn CookieCodec,
+    ) -> &'a dyn CookieCodec {
+        match &self.codecs {
+            Some(codecs) => codecs.get(name),
+            None => fallback,
+        }
     }
 
     /// Adds an __original__ cookie to the jar. These are never sent back to the
     /// user-agent, but are visible in the cookie jar.
     ///
-    /// If a cookie with the same name is already present it is replaced
-    /// (last-wins), matching [`add`](Self::add).
+    /// If an original cookie with the same name is already present it is replaced
+    /// (last-wins), matching [`add`](Self::add). An original cookie is hidden by any change
+    /// made to a cookie with the same name.
     pub fn add_original(&mut self, cookie: Cookie) {
-        self.cookies.replace(HashCookie::Original(cookie));
+        #[cfg(feature = "compression")]
+        let cookie = self.decompress(cookie);
+
+        let existing = self
+            .cookies
+            .iter_mut()
+            .find(|c| c.is_original() && c.cookie().name() == cookie.name());
+
+        match existing {
+            Some(existing) => *existing = JarCookie::Original(cookie),
+            None => self.cookies.push(JarCookie::Original(cookie)),
+        }
+    }
+
+    /// Enables compression of cookie values.
+    ///
+    /// Newly added cookies are compressed when their headers are created, if that makes them any
+    /// smaller. __Original__ cookies that are marked as compressed are decompressed, both the ones
+    /// that are already in the jar and the ones that are added later. Original cookies that fail
+    /// to decompress are kept as is, these were not compressed by the jar.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Compression, Cookie, CookieJar};
+    ///
+    /// let compression = Compression::new();
+    /// let compressed = compression.compress(&"a".repeat(100)).into_owned();
+    ///
+    /// let mut jar = CookieJar::from_original([Cookie::new("data", compressed)]);
+    /// jar.set_compression(compression);
+    ///
+    /// assert_eq!(jar.get("data").map(Cookie::value), Some("a".repeat(100).as_str()));
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn set_compression(&mut self, compression: Compression) {
+        self.compression = Some(compression);
+
+        let cookies = core::mem::take(&mut self.cookies);
+
+        self.cookies = cookies
+            .into_iter()
+            .map(|cookie| match cookie {
+                JarCookie::Original(cookie) => JarCookie::Original(self.decompress(cookie)),
+                cookie => cookie,
+            })
+            .collect();
+    }
+
+    #[cfg(feature = "compression")]
+    fn decompress(&self, mut cookie: Cookie) -> Cookie {
+        let Some(compression) = &self.compression else {
+            return cookie;
+        };
+
+        // Values that fail to decompress are kept as is.
+        if let Ok(Cow::Owned(value)) = compression.decompress(cookie.value()) {
+            cookie.set_value(value);
+        }
+        cookie
     }
 
     // Creates a `CookieJar` from an iterator of cookies. It is assumed that the cookies are
@@ -152,22 +461,93 @@ impl CookieJar {
     ///
     /// Parsing strips the `__Host-` / `__Secure-` prefix from the cookie name, so a cookie
     /// received (or built) with a prefix is looked up by its logical (unprefixed) name.
+    ///
+    /// If cookies with the same name were added under different paths or domains, the one that
+    /// was added last is returned.
     pub fn get(&self, name: &str) -> Option<&Cookie> {
-        self.cookies.get(name).and_then(|c| match c {
-            HashCookie::New(c) | HashCookie::Original(c) => Some(c),
-            HashCookie::Removal(_) => None,
+        if is_companion(name) {
+            return None;
+        }
+        self.find(name)
+    }
+
+    // Like `get`, but also finds the companion cookies of sliding sessions.
+    fn find(&self, name: &str) -> Option<&Cookie> {
+        let new = self.cookies.iter().rev().find_map(|cookie| match cookie {
+            JarCookie::New(c) if c.name() == name => Some(c),
+            _ => None,
+        });
+
+        new.or_else(|| self.visible_original(name))
+    }
+
+    /// Returns if a cookie with the given name is visible in the jar. This is the same as
+    /// `jar.get(name).is_some()`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns the number of visible cookies in the jar, these are the cookies returned by
+    /// [`iter`](Self::iter).
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns if the jar has no visible cookies.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Iterates over all visible cookies, in insertion order. These are the __original__ cookies
+    /// that were not removed or replaced and all newly added cookies.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar};
+    ///
+    /// let mut jar = CookieJar::from_cookie("theme=dark; lang=en");
+    /// jar.remove(Cookie::named("lang"));
+    /// jar.add(Cookie::new("session", "abc"));
+    ///
+    /// let names: Vec<_> = jar.iter().map(Cookie::name).collect();
+    /// assert_eq!(names, ["theme", "session"]);
+    /// ```
+    pub fn iter(&self) -> JarIter<'_> {
+        JarIter {
+            jar: self,
+            inner: self.cookies.iter(),
+        }
+    }
+
+    /// Iterates over all pending changes, in the order they were made. These are the cookies that
+    /// are sent back to the user-agent.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar, DeltaKind};
+    ///
+    /// let mut jar = CookieJar::from_cookie("theme=dark; lang=en");
+    /// jar.remove(Cookie::named("lang"));
+    /// jar.add(Cookie::new("session", "abc"));
+    ///
+    /// let delta: Vec<_> = jar.delta().map(|(kind, c)| (kind, c.name())).collect();
+    /// assert_eq!(delta, [(DeltaKind::Removal, "lang"), (DeltaKind::New, "session")]);
+    /// ```
+    pub fn delta(&self) -> impl Iterator<Item = (DeltaKind, &Cookie)> {
+        self.cookies.iter().filter_map(|cookie| match cookie {
+            JarCookie::Original(_) => None,
+            JarCookie::New(c) => Some((DeltaKind::New, c)),
+            JarCookie::Removal(c) => Some((DeltaKind::Removal, c)),
         })
     }
 
     /// Iterate over all changes. This returns all removed and newly created cookies.
+    ///
+    /// The cookies are encoded with the codecs set with [`set_codecs`](Self::set_codecs), or not
+    /// encoded at all if none are set.
     pub fn set_cookie_headers(&self) -> impl Iterator<Item = crate::Result<String>> {
-        self.cookies
-            .iter()
-            .filter_map(|c| match c {
-                HashCookie::Original(_) => None,
-                HashCookie::New(c) | HashCookie::Removal(c) => Some(c),
-            })
-            .map(Cookie::serialize)
+        self.iter_non_original()
+            .map(|cookie| cookie.serialize_with(self.codec_for(cookie.name(), &RawCodec)))
     }
 
     /// Removes the cookie from the local cookie store and issues a cookie with an Expires
@@ -175,30 +555,397 @@ impl CookieJar {
     ///
     /// If one of the `time`, `chrono` or `jiff` features are enabled, the Expires tag is set to the
     /// current time minus one year. If none of the those features are enabled, the Expires
-    /// attribute is set to 1 Jan 1970 00:00.
+    /// attribute is set to 1 Jan 1970 00:00. If a clock is set with `CookieJar::set_clock`, the
+    /// Expires attribute is set to one year before its current time.
     ///
     /// **To ensure a cookie is removed from the user-agent, set the `Path` and `Domain` attributes
     /// with the same values that were used to create the cookie.**
+    ///
+    /// Returns the newly added cookie with the same name, domain and path, or otherwise the
+    /// visible __original__ cookie with the same name.
+    ///
+    /// The [policy](Self::set_policy) of the jar, if any, is applied to the removal, so a cookie
+    /// that was added with the default Path and Domain attributes is removed with them.
+    ///
+    /// A removal without Path and Domain attributes also drops all newly added cookies with the
+    /// same name, whatever their Path and Domain attributes are. These were never sent to the
+    /// user-agent, so only the removal is sent.
+    ///
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add(Cookie::build("session", "abc").path("/"));
+    /// jar.remove(Cookie::named("session"));
+    ///
+    /// assert_eq!(jar.get("session"), None);
+    /// assert_eq!(jar.set_cookie_headers().count(), 1);
+    /// ```
     pub fn remove(&mut self, cookie: impl Into<Cookie>) -> Option<Cookie> {
-        let cookie = HashCookie::Removal(cookie.into().into_remove());
-        self.cookies.replace(cookie).and_then(|c| match c {
-            HashCookie::Original(cookie) => Some(cookie),
-            HashCookie::New(cookie) => Some(cookie),
-            HashCookie::Removal(_) => None,
-        })
+        let cookie = cookie.into().into_remove(self.removal_expires());
+        let cookie = self.apply_policy(cookie);
+        let original = self.visible_original(cookie.name()).cloned();
+
+        let mut dropped = None;
+        if cookie.domain().is_none() && cookie.path().is_none() {
+            dropped = self.drop_new(&cookie);
+        }
+
+        // The companion of a sliding session cookie is not visible, so it's removed together
+        // with the cookie.
+        let companion = companion_name(cookie.name());
+        if self.find(&companion).is_some() {
+            let mut removal = cookie.clone();
+            removal.set_name(companion);
+            self.remove(removal);
+        }
+
+        match self.insert_change(JarCookie::Removal(cookie)) {
+            Some(JarCookie::New(cookie)) => Some(cookie),
+            _ => dropped.or(original),
+        }
     }
 
     /// Adds a cookie to the jar. If a cookie with the same name is already in the jar, it is
     /// replaced with the given cookie.
+    ///
+    /// A newly added cookie only replaces an earlier change if it also has the same domain and
+    /// path, otherwise both are sent to the user-agent.
+    ///
+    /// The [policy](Self::set_policy) of the jar, if any, is applied to the cookie first.
     pub fn add(&mut self, cookie: impl Into<Cookie>) {
-        self.cookies.replace(HashCookie::New(cookie.into()));
+        let cookie = self.apply_policy(cookie.into());
+        self.insert_change(JarCookie::New(cookie));
+    }
+
+    /// Extends the lifetime of a sliding session cookie. Returns if the cookie was re-issued.
+    ///
+    /// The `Cookie` header doesn't carry the attributes of a cookie, so the expiry is kept in a
+    /// companion cookie named `{name}~expires`, as a signed Unix timestamp. Cookie names ending
+    /// in `~expires` are reserved for these companion cookies, they are not returned by
+    /// [`get`](Self::get) or [`iter`](Self::iter) and are removed together with their cookie. The value of the
+    /// cookie itself is never changed. When the cookie expires in less than `refresh_threshold`
+    /// according to the [clock](Self::set_clock) of the jar, or if it has no companion cookie
+    /// yet, both cookies are [added](Self::add) again with a Max-Age of `ttl`. Otherwise the jar
+    /// is left untouched, so no `Set-Cookie` header is sent on every response.
+    ///
+    /// Returns `false` if there is no visible cookie with the given name.
+    ///
+    /// The timestamp is not authenticated, only use it to decide when to re-issue the cookie and
+    /// keep validating the session itself on the server.
+    ///
+    /// **The `Path` and `Domain` attributes are not sent in the `Cookie` header, set them with a
+    /// [policy](Self::set_policy) so the re-issued cookies replace the original ones.** Cookies
+    /// with a `__Host-` or `__Secure-` prefix are re-issued with the attributes the prefix
+    /// requires.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use cookie_monster::{CookieJar, FixedClock};
+    ///
+    /// let ttl = Duration::from_secs(3600);
+    /// let threshold = Duration::from_secs(600);
+    ///
+    /// let mut jar = CookieJar::from_cookie("session=abc; session~expires=1000");
+    /// jar.set_clock(FixedClock::from_unix_timestamp(0));
+    ///
+    /// // Expires in 1000 seconds, no need to re-issue the cookie.
+    /// assert!(!jar.touch("session", ttl, threshold));
+    /// assert_eq!(jar.delta().count(), 0);
+    ///
+    /// jar.set_clock(FixedClock::from_unix_timestamp(500));
+    /// assert!(jar.touch("session", ttl, threshold));
+    ///
+    /// let session = jar.get("session").unwrap();
+    /// assert_eq!(session.value(), "abc");
+    /// assert_eq!(session.max_age_secs(), Some(3600));
+    /// assert_eq!(jar.sliding_expiry("session"), Some(4100));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn touch(
+        &mut self,
+        name: &str,
+        ttl: impl Into<MaxAge>,
+        refresh_threshold: impl Into<MaxAge>,
+    ) -> bool {
+        let Some(cookie) = self.get(name) else {
+            return false;
+        };
+
+        let now = Expires::from(self.clock().now())
+            .unix_timestamp()
+            .unwrap_or_default();
+
+        if let Some(expiry) = self.sliding_expiry(name) {
+            if expiry.saturating_sub(now) >= refresh_threshold.into().as_secs() {
+                return false;
+            }
+        }
+
+        let ttl = ttl.into();
+        let mut cookie = cookie.clone();
+        cookie.set_max_age(ttl);
+        cookie.apply_prefix_requirements();
+
+        let mut companion = cookie.clone();
+        companion.set_name(companion_name(name));
+        companion.set_value(format!("{}", now.saturating_add(ttl.as_secs())));
+        companion.set_quoted(false);
+
+        self.add(cookie);
+        self.add(companion);
+        true
+    }
+
+    /// Returns the expiry of a sliding session cookie as a signed Unix timestamp in seconds, read
+    /// from the companion cookie that `CookieJar::touch` sets.
+    pub fn sliding_expiry(&self, name: &str) -> Option<i64> {
+        self.find(&companion_name(name))?.value().parse().ok()
+    }
+
+    /// Retains only the visible cookies for which the predicate returns `true`. All other visible
+    /// cookies are [removed](Self::remove), so a removal is sent to the user-agent for each of
+    /// them.
+    pub fn retain(&mut self, mut f: impl FnMut(&Cookie) -> bool) {
+        let removed: Vec<Cookie> = self.iter().filter(|c| !f(c)).cloned().collect();
+
+        for cookie in removed {
+            self.remove(cookie);
+        }
+    }
+
+    /// Removes all visible cookies. A removal is sent to the user-agent for every cookie that was
+    /// visible in the jar.
+    pub fn clear(&mut self) {
+        self.retain(|_| false);
+    }
+
+    /// Discards all pending changes, the jar only contains the __original__ cookies again.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cookie_monster::{Cookie, CookieJar};
+    ///
+    /// let mut jar = CookieJar::from_cookie("theme=dark");
+    /// jar.clear();
+    /// assert!(jar.is_empty());
+    ///
+    /// jar.reset_delta();
+    /// assert_eq!(jar.get("theme").map(Cookie::value), Some("dark"));
+    /// assert_eq!(jar.delta().count(), 0);
+    /// ```
+    pub fn reset_delta(&mut self) {
+        self.cookies.retain(JarCookie::is_original);
+        self.changed.clear();
     }
 
-    #[allow(unused)]
-    pub(crate) fn iter_non_original(&self) -> impl Iterator<Item = &Cookie> {
-        self.cookies.iter().flat_map(|cookie| match cookie {
-            HashCookie::Original(_) => None,
-            HashCookie::New(cookie) | HashCookie::Removal(cookie) => Some(cookie),
+    // An original cookie is shadowed by any change made to a cookie with the same name.
+    fn is_shadowed(&self, original: &Cookie) -> bool {
+        self.changed.contains(original.name())
+    }
+
+    // Drops the newly added cookies with the same name as the removal, but another identity.
+    // Returns the last dropped cookie.
+    fn drop_new(&mut self, removal: &Cookie) -> Option<Cookie> {
+        let mut dropped = None;
+
+        self.cookies.retain(|cookie| match cookie {
+            JarCookie::New(c) if c.name() == removal.name() && !c.same_identity(removal) => {
+                dropped = Some(c.clone());
+                false
+            }
+            _ => true,
+        });
+
+        dropped
+    }
+
+    fn visible_original(&self, name: &str) -> Option<&Cookie> {
+        self.cookies.iter().find_map(|cookie| match cookie {
+            JarCookie::Original(c) if c.name() == name && !self.is_shadowed(c) => Some(c),
+            _ => None,
         })
     }
+
+    // Replaces the change for the same cookie in place, or appends it. Returns the replaced change.
+    fn insert_change(&mut self, change: JarCookie) -> Option<JarCookie> {
+        let existing = self
+            .cookies
+            .iter_mut()
+            .find(|c| !c.is_original() && c.cookie().same_identity(change.cookie()));
+
+        match existing {
+            Some(existing) => Some(core::mem::replace(existing, change)),
+            None => {
+                self.changed.insert(change.cookie().name().into());
+                self.cookies.push(change);
+                None
+            }
+        }
+    }
+
+    // Iterates over the cookies that should be sent back to the user-agent, as they should be
+    // serialized.
+    pub(crate) fn iter_non_original(&self) -> impl Iterator<Item = Cow<'_, Cookie>> {
+        let consent = self.consent();
+
+        self.cookies
+            .iter()
+            .flat_map(move |cookie| match cookie {
+                JarCookie::Original(_) => None,
+                JarCookie::New(cookie) if consent.is_some_and(|c| !c.allows(cookie.category())) => {
+                    Some(Cow::Owned(
+                        cookie.clone().into_remove(self.removal_expires()),
+                    ))
+                }
+                JarCookie::New(cookie) => Some(self.compress(cookie)),
+                JarCookie::Removal(cookie) => Some(Cow::Borrowed(cookie)),
+            })
+            .map(|cookie| match &self.policy {
+                Some(policy) => {
+                    let mut cookie = cookie.into_owned();
+                    policy.apply(&mut cookie);
+                    Cow::Owned(cookie)
+                }
+                None => cookie,
+            })
+            .map(|cookie| match self.dev_mode {
+                true => Cow::Owned(cookie.into_owned().into_insecure()),
+                false => cookie,
+            })
+    }
+
+    fn compress<'c>(&self, cookie: &'c Cookie) -> Cow<'c, Cookie> {
+        #[cfg(feature = "compression")]
+        if let Some(compression) = &self.compression {
+            if let Cow::Owned(value) = compression.compress(cookie.value()) {
+                let mut cookie = cookie.clone();
+                cookie.set_value(value);
+                return Cow::Owned(cookie);
+            }
+        }
+
+        Cow::Borrowed(cookie)
+    }
+}
+
+// The name of the companion cookie that holds the expiry of a sliding session cookie.
+fn companion_name(name: &str) -> String {
+    format!("{name}{SLIDING_SUFFIX}")
+}
+
+fn is_companion(name: &str) -> bool {
+    name.ends_with(SLIDING_SUFFIX)
+}
+
+/// The kind of change made to a cookie in a [`CookieJar`], returned by [`CookieJar::delta`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeltaKind {
+    /// The cookie was added with [`CookieJar::add`].
+    New,
+    /// The cookie was removed with [`CookieJar::remove`].
+    Removal,
+}
+
+/// An iterator over the visible cookies in a [`CookieJar`], created by [`CookieJar::iter`].
+#[derive(Debug, Clone)]
+pub struct JarIter<'a> {
+    jar: &'a CookieJar,
+    inner: core::slice::Iter<'a, JarCookie>,
+}
+
+impl<'a> Iterator for JarIter<'a> {
+    type Item = &'a Cookie;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.inner.next()? {
+                JarCookie::New(cookie) if !is_companion(cookie.name()) => return Some(cookie),
+                JarCookie::Original(cookie)
+                    if !self.jar.is_shadowed(cookie) && !is_companion(cookie.name()) =>
+                {
+                    return Some(cookie);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// An owning iterator over the visible cookies in a [`CookieJar`], created by
+/// [`CookieJar::into_iter`].
+#[derive(Debug)]
+pub struct JarIntoIter {
+    inner: alloc::vec::IntoIter<Cookie>,
+}
+
+impl Iterator for JarIntoIter {
+    type Item = Cookie;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<'a> IntoIterator for &'a CookieJar {
+    type Item = &'a Cookie;
+    type IntoIter = JarIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for CookieJar {
+    type Item = Cookie;
+    type IntoIter = JarIntoIter;
+
+    /// Turns the jar into an iterator over its visible cookies, the pending changes are dropped.
+    fn into_iter(self) -> Self::IntoIter {
+        let visible: Vec<bool> = self
+            .cookies
+            .iter()
+            .map(|cookie| match cookie {
+                JarCookie::New(c) => !is_companion(c.name()),
+                JarCookie::Original(c) => !self.is_shadowed(c) && !is_companion(c.name()),
+                JarCookie::Removal(_) => false,
+            })
+            .collect();
+
+        let cookies = self
+            .cookies
+            .into_iter()
+            .zip(visible)
+            .filter(|(_, visible)| *visible)
+            .map(|(cookie, _)| match cookie {
+                JarCookie::Original(c) | JarCookie::New(c) | JarCookie::Removal(c) => c,
+            })
+            .collect::<Vec<_>>();
+
+        JarIntoIter {
+            inner: cookies.into_iter(),
+        }
+    }
+}
+
+impl<C: Into<Cookie>> Extend<C> for CookieJar {
+    /// Adds all cookies to the jar, as if they were added with [`CookieJar::add`].
+    fn extend<T: IntoIterator<Item = C>>(&mut self, iter: T) {
+        for cookie in iter {
+            self.add(cookie);
+        }
+    }
+}
+
+impl<C: Into<Cookie>> FromIterator<C> for CookieJar {
+    /// Creates a jar with all cookies added as new cookies, as if they were added with
+    /// [`CookieJar::add`]. Use [`CookieJar::from_original`] to create a jar from __original__
+    /// cookies.
+    fn from_iter<T: IntoIterator<Item = C>>(iter: T) -> Self {
+        let mut jar = CookieJar::new();
+        jar.extend(iter);
+        jar
+    }
 }
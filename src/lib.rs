@@ -26,6 +26,15 @@
 //!```
 //!
 //! # Features
+//! * `std` (enabled by default)
+//!
+//!   Uses the standard library. Without it the crate only depends on `alloc`, which makes it
+//!   usable in `no_std` environments that have an allocator. The `time`, `chrono`, `jiff`,
+//!   `compression`, `http` and `axum` features require `std`.
+//!
+//!   The Expires attribute can be set from a `std::time::SystemTime`, none of the datetime
+//!   features are needed to emit `Expires=`.
+//!
 //! * `jiff`
 //!
 //!   Adds support for the [jiff](https://docs.rs/jiff/latest/jiff/) crate.
@@ -48,6 +57,10 @@
 //!
 //!   Parse/serialize [`Cookie`]s that are percent-encoded.
 //!
+//! * `compression`
+//!
+//!   Compress [`Cookie`] values with deflate, see `Compression`.
+//!
 //! * `axum`
 //!
 //!   Adds integration with the [axum](https://docs.rs/axum/latest/axum/) crate.  
@@ -62,6 +75,11 @@
 //!   Create a [`CookieJar`] from a [`HeaderMap`](https://docs.rs/http/latest/http/header/struct.HeaderMap.html).
 //!   Write a [`CookieJar`] to a [`HeaderMap`](https://docs.rs/http/latest/http/header/struct.HeaderMap.html).
 //!
+//! * `test-util`
+//!
+//!   Adds a `TestClient` that wraps a [`tower::Service`](https://docs.rs/tower/latest/tower/trait.Service.html),
+//!   such as an axum `Router`, and carries cookies across requests like a browser does.
+//!
 //!
 //! # Axum example
 //!
@@ -97,6 +115,17 @@
 //! ### Honorable mention
 //! This crate takes a lot of inspiration from the [cookie](https://crates.io/crates/cookie) crate.
 
+#![no_std]
+
+extern crate alloc;
+
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+mod base64;
+#[cfg(feature = "std")]
+mod clock;
+mod codec;
 mod cookie;
 mod error;
 mod jar;
@@ -105,10 +134,42 @@ mod util;
 #[cfg(feature = "axum")]
 mod axum;
 
+#[cfg(feature = "axum")]
+pub use axum::{
+    DevMode, SetCookieErrorFuture, SetCookieErrorLayer, SetCookieErrorPolicy,
+    SetCookieErrorService, SetCookieRejection,
+};
+
 #[cfg(feature = "http")]
 mod http;
 
-pub use cookie::{Cookie, CookieBuilder, expires::Expires, same_site::SameSite};
-pub use error::Error;
-pub(crate) type Result<T, E = Error> = ::std::result::Result<T, E>;
-pub use jar::CookieJar;
+#[cfg(feature = "http")]
+pub use http::SetCookieError;
+
+#[cfg(feature = "compression")]
+mod compression;
+
+#[cfg(feature = "test-util")]
+mod test_util;
+
+#[cfg(feature = "test-util")]
+pub use test_util::TestClient;
+
+#[cfg(feature = "std")]
+pub use clock::{Clock, FixedClock, SystemClock};
+#[cfg(feature = "percent-encode")]
+pub use codec::PercentCodec;
+pub use codec::{Base64UrlCodec, CookieCodec, CookieCodecs, RawCodec};
+#[cfg(feature = "compression")]
+pub use compression::Compression;
+pub use cookie::{
+    Cookie, CookieBuilder, CookieRef, CookieTemplate, MaxAge,
+    category::{Consent, CookieCategory},
+    expires::Expires,
+    options::{NonUtf8, ParseOptions, ParseProfile},
+    policy::CookiePolicy,
+    same_site::SameSite,
+};
+pub use error::{Attribute, Error, ErrorKind, Phase};
+pub(crate) type Result<T, E = Error> = ::core::result::Result<T, E>;
+pub use jar::{CookieJar, DeltaKind, JarIntoIter, JarIter};
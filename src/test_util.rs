@@ -0,0 +1,284 @@
+use core::future::poll_fn;
+use std::{borrow::ToOwned, string::String, sync::Arc, time::SystemTime, vec::Vec};
+
+use http::{
+    HeaderValue, Request, Response,
+    header::{COOKIE, SET_COOKIE},
+};
+use tower_service::Service;
+
+use crate::{Clock, Cookie, CookieCodecs, Expires, PercentCodec, SameSite};
+
+/// A client for tests that carries cookies across requests, like a browser does.
+///
+/// The client wraps a [`tower::Service`](https://docs.rs/tower/latest/tower/trait.Service.html),
+/// such as an axum `Router`. Every request sent with [`oneshot`](Self::oneshot) gets a `Cookie`
+/// header with the stored cookies that match its path, and the `Set-Cookie` headers of the
+/// response are applied to the store. Expired cookies are removed.
+///
+/// The client acts like a browser on a secure origin, the Domain, Secure and SameSite attributes
+/// are stored but not used to select the cookies that are sent.
+///
+/// Cookie names and values are percent-decoded, like [`CookieJar::from_headers`] does. Use
+/// [`set_codecs`](Self::set_codecs) if the server uses other codecs.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "axum")]
+/// # async fn login_flow() {
+/// use axum::{Router, body::Body, http::Request, routing::get};
+/// use cookie_monster::{Cookie, CookieJar, TestClient};
+///
+/// async fn login(mut jar: CookieJar) -> CookieJar {
+///     jar.add(Cookie::build("session", "abc").path("/"));
+///     jar
+/// }
+///
+/// async fn me(jar: CookieJar) -> String {
+///     jar.get("session").map(|c| c.value().to_owned()).unwrap_or_default()
+/// }
+///
+/// let app = Router::new().route("/login", get(login)).route("/me", get(me));
+/// let mut client = TestClient::new(app);
+///
+/// let request = Request::get("/login").body(Body::empty()).unwrap();
+/// client.oneshot(request).await.unwrap();
+/// assert_eq!(client.cookie("session").map(Cookie::value), Some("abc"));
+///
+/// // The session cookie is sent with the next request.
+/// let request = Request::get("/me").body(Body::empty()).unwrap();
+/// client.oneshot(request).await.unwrap();
+/// # }
+/// ```
+///
+/// [`CookieJar::from_headers`]: crate::CookieJar::from_headers
+#[derive(Debug)]
+pub struct TestClient<S> {
+    service: S,
+    cookies: Vec<StoredCookie>,
+    codecs: CookieCodecs,
+    clock: Option<Arc<dyn Clock>>,
+}
+
+#[derive(Debug)]
+struct StoredCookie {
+    cookie: Cookie,
+    // When the cookie expires, `None` for session cookies.
+    expiry: Option<SystemTime>,
+}
+
+impl<S> TestClient<S> {
+    /// Creates a client without any cookies.
+    pub fn new(service: S) -> Self {
+        Self {
+            service,
+            cookies: Vec::new(),
+            codecs: CookieCodecs::new(PercentCodec),
+            clock: None,
+        }
+    }
+
+    /// Sets the codecs used to decode the `Set-Cookie` headers and to encode the `Cookie` header.
+    pub fn set_codecs(&mut self, codecs: CookieCodecs) {
+        self.codecs = codecs;
+    }
+
+    /// Sets the clock used to expire cookies. Defaults to the [`SystemClock`](crate::SystemClock).
+    pub fn set_clock(&mut self, clock: impl Clock) {
+        self.clock = Some(Arc::new(clock));
+    }
+
+    /// Returns the stored cookie with the given name, if it's not expired. If cookies with the
+    /// same name are stored under different paths or domains, the one that was stored last is
+    /// returned.
+    pub fn cookie(&self, name: &str) -> Option<&Cookie> {
+        self.cookies().filter(|cookie| cookie.name() == name).last()
+    }
+
+    /// Iterates over the stored cookies that are not expired, in the order they were stored.
+    pub fn cookies(&self) -> impl Iterator<Item = &Cookie> {
+        let now = self.now();
+
+        self.cookies
+            .iter()
+            .filter(move |stored| stored.is_live(now))
+            .map(|stored| &stored.cookie)
+    }
+
+    /// Stores a cookie as if it was received in a `Set-Cookie` header. Cookies without a Path
+    /// attribute are sent with every request.
+    pub fn add_cookie(&mut self, cookie: impl Into<Cookie>) {
+        self.store(cookie.into(), "/");
+    }
+
+    /// Removes all stored cookies.
+    pub fn clear_cookies(&mut self) {
+        self.cookies.clear();
+    }
+
+    /// Returns a reference to the wrapped service.
+    pub fn service(&self) -> &S {
+        &self.service
+    }
+
+    /// Returns a mutable reference to the wrapped service.
+    pub fn service_mut(&mut self) -> &mut S {
+        &mut self.service
+    }
+
+    /// Consumes the client and returns the wrapped service.
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+
+    /// Sends the request with the stored cookies and stores the cookies of the response.
+    ///
+    /// A `Cookie` header is appended to the request if any stored cookie matches its path. The
+    /// cookies are ordered by path length, longest first.
+    pub async fn oneshot<B, ResBody>(
+        &mut self,
+        mut request: Request<B>,
+    ) -> Result<Response<ResBody>, S::Error>
+    where
+        S: Service<Request<B>, Response = Response<ResBody>>,
+    {
+        let path = request.uri().path().to_owned();
+
+        if let Some(header) = self.cookie_header(&path) {
+            request.headers_mut().append(COOKIE, header);
+        }
+
+        poll_fn(|cx| self.service.poll_ready(cx)).await?;
+        let response = self.service.call(request).await?;
+
+        for header in response.headers().get_all(SET_COOKIE) {
+            let Some(cookie) = header.to_str().ok().and_then(|h| self.parse_set_cookie(h)) else {
+                continue;
+            };
+            self.store(cookie, &path);
+        }
+
+        Ok(response)
+    }
+
+    fn now(&self) -> SystemTime {
+        match &self.clock {
+            Some(clock) => clock.now(),
+            None => crate::SystemClock.now(),
+        }
+    }
+
+    fn cookie_header(&self, request_path: &str) -> Option<HeaderValue> {
+        let now = self.now();
+        let mut cookies: Vec<&Cookie> = self
+            .cookies
+            .iter()
+            .filter(|stored| stored.is_live(now))
+            .map(|stored| &stored.cookie)
+            .filter(|cookie| path_matches(cookie.path().unwrap_or("/"), request_path))
+            .collect();
+
+        if cookies.is_empty() {
+            return None;
+        }
+
+        cookies.sort_by_key(|cookie| core::cmp::Reverse(cookie.path().map(str::len)));
+
+        let mut header = String::new();
+        for cookie in cookies {
+            if !header.is_empty() {
+                header.push_str("; ");
+            }
+            cookie.write_cookie_pair(self.codecs.get(cookie.name()), &mut header);
+        }
+
+        HeaderValue::from_str(&header).ok()
+    }
+
+    // Parses a `Set-Cookie` header, unknown attributes and invalid attribute values are ignored.
+    fn parse_set_cookie(&self, header: &str) -> Option<Cookie> {
+        let mut parts = header.split(';');
+        let name_value = parts.next()?.trim();
+
+        // The codec is picked by the logical (unprefixed) name.
+        let (name, _) = name_value.split_once('=')?;
+        let (_, name) = crate::cookie::prefix::split_prefix(name.trim().into());
+        let mut cookie = Cookie::parse_cookie_with(name_value, self.codecs.get(&name)).ok()?;
+
+        for attribute in parts {
+            let (key, value) = match attribute.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attribute.trim(), ""),
+            };
+
+            match key.to_ascii_lowercase().as_str() {
+                "max-age" => {
+                    if let Ok(max_age) = value.parse() {
+                        cookie.set_max_age_secs(max_age);
+                    }
+                }
+                "expires" => {
+                    if let Ok(expires) = value.parse::<Expires>() {
+                        cookie.set_expires(expires);
+                    }
+                }
+                "domain" => cookie.set_domain(value.to_owned()),
+                "path" => cookie.set_path(value.to_owned()),
+                "secure" => cookie.set_secure(true),
+                "httponly" => cookie.set_http_only(true),
+                "partitioned" => cookie.set_partitioned(true),
+                "samesite" => cookie.set_same_site(match value.to_ascii_lowercase().as_str() {
+                    "strict" => Some(SameSite::Strict),
+                    "lax" => Some(SameSite::Lax),
+                    "none" => Some(SameSite::None),
+                    _ => None,
+                }),
+                _ => {}
+            }
+        }
+
+        Some(cookie)
+    }
+
+    // Replaces the cookie with the same identity, or removes it if the new cookie is expired.
+    fn store(&mut self, mut cookie: Cookie, request_path: &str) {
+        if !cookie.path().is_some_and(|path| path.starts_with('/')) {
+            cookie.set_path(default_path(request_path).to_owned());
+        }
+
+        let now = self.now();
+        let stored = StoredCookie {
+            expiry: cookie.effective_expiry(now),
+            cookie,
+        };
+
+        self.cookies
+            .retain(|existing| !existing.cookie.same_identity(&stored.cookie));
+
+        if stored.is_live(now) {
+            self.cookies.push(stored);
+        }
+    }
+}
+
+impl StoredCookie {
+    fn is_live(&self, now: SystemTime) -> bool {
+        self.expiry.is_none_or(|expiry| expiry > now)
+    }
+}
+
+// The default Path of a cookie, see RFC 6265 §5.1.4.
+fn default_path(request_path: &str) -> &str {
+    match request_path.rfind('/') {
+        Some(0) | None => "/",
+        Some(i) => &request_path[..i],
+    }
+}
+
+// If the cookie path matches the request path, see RFC 6265 §5.1.4.
+fn path_matches(cookie_path: &str, request_path: &str) -> bool {
+    match request_path.strip_prefix(cookie_path) {
+        Some(rest) => rest.is_empty() || cookie_path.ends_with('/') || rest.starts_with('/'),
+        None => false,
+    }
+}
@@ -1,4 +1,27 @@
-use std::borrow::Cow;
+use alloc::{borrow::Cow, boxed::Box, string::String, sync::Arc};
+use core::{
+    net::{IpAddr, Ipv4Addr},
+    ops::Deref,
+};
+
+// The buffer a parsed cookie points into. A cookie parsed on its own keeps its input, cookies
+// parsed from the same `Cookie` header share a single buffer.
+#[derive(Clone)]
+pub(crate) enum RawBuf {
+    Owned(Box<str>),
+    Shared(Arc<str>),
+}
+
+impl Deref for RawBuf {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            RawBuf::Owned(buf) => buf,
+            RawBuf::Shared(buf) => buf,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub(crate) enum TinyStr {
@@ -55,3 +78,34 @@ impl Default for TinyStr {
         Self::empty()
     }
 }
+
+// Returns if the host, with an optional port, is `localhost`, a `.localhost` subdomain or a
+// loopback IP address.
+pub(crate) fn is_loopback_host(host: &str) -> bool {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return ip.is_loopback();
+    }
+
+    let host = match host.strip_prefix('[') {
+        // An IPv6 address, with an optional port.
+        Some(rest) => {
+            return rest
+                .split_once(']')
+                .and_then(|(ip, _)| ip.parse::<IpAddr>().ok())
+                .is_some_and(|ip| ip.is_loopback());
+        }
+        None => host.split_once(':').map_or(host, |(host, _)| host),
+    };
+
+    if let Ok(ip) = host.parse::<Ipv4Addr>() {
+        return ip.is_loopback();
+    }
+
+    let host = host.strip_suffix('.').unwrap_or(host);
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .len()
+            .checked_sub(".localhost".len())
+            .and_then(|i| host.get(i..))
+            .is_some_and(|suffix| suffix.eq_ignore_ascii_case(".localhost"))
+}
@@ -0,0 +1,173 @@
+use cookie_monster::{
+    Base64UrlCodec, Cookie, CookieCodec, CookieCodecs, CookieJar, ErrorKind, RawCodec,
+};
+
+#[cfg(feature = "axum")]
+mod util;
+
+#[test]
+fn raw_codec() {
+    let cookie = Cookie::new("foo", "a%zz");
+    assert_eq!(cookie.serialize_with(&RawCodec).as_deref(), Ok("foo=a%zz"));
+
+    let cookie = Cookie::parse_cookie_with("foo=a%zz", &RawCodec).unwrap();
+    assert_eq!(cookie.value(), "a%zz");
+
+    assert_eq!(
+        Cookie::new("foo", "a b")
+            .serialize_with(&RawCodec)
+            .map_err(|e| e.kind()),
+        Err(ErrorKind::InvalidValue(' '))
+    );
+}
+
+#[cfg(feature = "percent-encode")]
+#[test]
+fn percent_codec() {
+    use cookie_monster::PercentCodec;
+
+    let cookie = Cookie::new("foo bar", "a;b");
+    assert_eq!(
+        cookie.serialize_with(&PercentCodec),
+        cookie.serialize_encoded()
+    );
+
+    let cookie = Cookie::parse_cookie_with("foo%20bar=a%3Bb", &PercentCodec).unwrap();
+    assert_eq!((cookie.name(), cookie.value()), ("foo bar", "a;b"));
+
+    assert_eq!(
+        Cookie::parse_cookie_with("foo=%FF", &PercentCodec).map_err(|e| e.kind()),
+        Err(ErrorKind::PercentDecodeError)
+    );
+}
+
+#[test]
+fn base64url_codec() {
+    let cookie = Cookie::build("data", "{\"id\": 1}").path("/").build();
+    let serialized = cookie.serialize_with(&Base64UrlCodec).unwrap();
+    assert_eq!(serialized, "data=eyJpZCI6IDF9; Path=/");
+
+    let parsed = Cookie::parse_cookie_with(serialized, &Base64UrlCodec).unwrap();
+    assert_eq!((parsed.name(), parsed.value()), ("data", "{\"id\": 1}"));
+
+    let error = Cookie::parse_cookie_with("data=not*base64", &Base64UrlCodec).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Base64DecodeError);
+    assert_eq!(error.name(), Some("data"));
+}
+
+#[test]
+fn codec_overrides() {
+    let codecs = CookieCodecs::new(RawCodec)
+        .with("data", RawCodec)
+        .with("data", Base64UrlCodec);
+
+    assert_eq!(
+        codecs.get("data").encode_value("hello"),
+        Base64UrlCodec.encode_value("hello")
+    );
+    assert_eq!(codecs.get("other").encode_value("hello"), "hello");
+}
+
+#[test]
+fn jar_decodes_with_codecs() {
+    let codecs = CookieCodecs::new(RawCodec).with("data", Base64UrlCodec);
+    let jar = CookieJar::from_cookie_with("legacy=a%zz; data=aGVsbG8; broken=a; data=*", codecs);
+
+    // The last `data` cookie fails to decode, so the first one is kept.
+    assert_eq!(jar.get("legacy").map(Cookie::value), Some("a%zz"));
+    assert_eq!(jar.get("data").map(Cookie::value), Some("hello"));
+    assert_eq!(jar.get("broken").map(Cookie::value), Some("a"));
+}
+
+#[test]
+fn jar_overrides_match_unprefixed_name() {
+    let codecs = CookieCodecs::new(RawCodec).with("data", Base64UrlCodec);
+    let jar = CookieJar::from_cookie_with("__Host-data=aGVsbG8", codecs);
+
+    assert_eq!(jar.get("data").map(Cookie::value), Some("hello"));
+}
+
+#[test]
+fn jar_encodes_with_codecs() {
+    let mut jar = CookieJar::new();
+    jar.add(Cookie::new("data", "hello"));
+
+    let headers: Vec<_> = jar.set_cookie_headers().collect();
+    assert_eq!(headers, [Ok("data=hello".to_string())]);
+
+    jar.set_codecs(CookieCodecs::new(RawCodec).with("data", Base64UrlCodec));
+    let headers: Vec<_> = jar.set_cookie_headers().collect();
+    assert_eq!(headers, [Ok("data=aGVsbG8".to_string())]);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_codecs() {
+    use http::{HeaderMap, HeaderValue};
+
+    let mut headers = HeaderMap::new();
+    headers.append(
+        "cookie",
+        HeaderValue::from_static("legacy=a%FF; data=aGVsbG8"),
+    );
+
+    // The default percent-decoding drops the legacy cookie.
+    let jar = CookieJar::from_headers(&headers);
+    assert!(jar.get("legacy").is_none());
+
+    let codecs = CookieCodecs::new(RawCodec).with("data", Base64UrlCodec);
+    let mut jar = CookieJar::from_headers_with(&headers, codecs);
+    assert_eq!(jar.get("legacy").map(Cookie::value), Some("a%FF"));
+    assert_eq!(jar.get("data").map(Cookie::value), Some("hello"));
+
+    jar.add(Cookie::new("data", "world"));
+    jar.add(Cookie::new("legacy", "b%FF"));
+
+    let mut headers = HeaderMap::new();
+    jar.write_cookies(&mut headers);
+
+    let set_cookie: Vec<_> = headers.get_all("set-cookie").iter().collect();
+    assert_eq!(set_cookie, ["data=d29ybGQ", "legacy=b%FF"]);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_raw() {
+    use http::{HeaderMap, HeaderValue};
+
+    let mut headers = HeaderMap::new();
+    headers.append(
+        "cookie",
+        HeaderValue::from_static("legacy=a%FF; name=a%20b"),
+    );
+
+    let mut jar = CookieJar::from_headers_raw(&headers);
+    assert_eq!(jar.get("legacy").map(Cookie::value), Some("a%FF"));
+    assert_eq!(jar.get("name").map(Cookie::value), Some("a%20b"));
+    assert!(jar.codecs().is_none());
+
+    jar.add(Cookie::new("legacy", "b%FF"));
+    jar.add(Cookie::new("invalid", "a b"));
+    jar.set_codecs(CookieCodecs::new(Base64UrlCodec));
+
+    let mut headers = HeaderMap::new();
+    jar.write_cookies_raw(&mut headers);
+
+    let set_cookie: Vec<_> = headers.get_all("set-cookie").iter().collect();
+    assert_eq!(set_cookie, ["legacy=b%FF"]);
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_extracts_with_codecs() {
+    use axum::http::Request;
+
+    let request = Request::builder()
+        .header("cookie", "legacy=a%zz")
+        .extension(CookieCodecs::raw())
+        .body(())
+        .unwrap();
+    let jar = util::extract_jar(request);
+
+    assert_eq!(jar.get("legacy").map(Cookie::value), Some("a%zz"));
+}
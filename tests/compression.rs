@@ -0,0 +1,107 @@
+use cookie_monster::{Compression, Cookie, CookieJar, ErrorKind};
+
+#[test]
+fn round_trip() {
+    let compression = Compression::new();
+    let value = "session-data,".repeat(20);
+
+    let compressed = compression.compress(&value);
+    assert!(Compression::is_compressed(&compressed));
+    assert!(compressed.len() < value.len());
+
+    assert_eq!(
+        compression.decompress(&compressed).as_deref(),
+        Ok(value.as_str())
+    );
+}
+
+#[test]
+fn small_values_are_left_alone() {
+    let compression = Compression::new();
+
+    assert_eq!(compression.compress("abc"), "abc");
+    assert_eq!(compression.decompress("abc").as_deref(), Ok("abc"));
+}
+
+#[test]
+fn invalid_values() {
+    let compression = Compression::new();
+
+    assert_eq!(
+        compression.decompress("~z!!").map_err(|e| e.kind()),
+        Err(ErrorKind::DecompressError)
+    );
+    assert_eq!(
+        compression.decompress("~zAAAA").map_err(|e| e.kind()),
+        Err(ErrorKind::DecompressError)
+    );
+}
+
+#[test]
+fn decompression_is_bounded() {
+    let value = "a".repeat(10_000);
+    let compressed = Compression::new().compress(&value);
+
+    let bounded = Compression::new().max_decompressed_size(1024);
+    assert_eq!(
+        bounded.decompress(&compressed).map_err(|e| e.kind()),
+        Err(ErrorKind::DecompressedTooLarge)
+    );
+
+    let exact = Compression::new().max_decompressed_size(10_000);
+    assert_eq!(exact.decompress(&compressed).as_deref(), Ok(value.as_str()));
+}
+
+#[test]
+fn jar_compresses_new_cookies() {
+    let value = "a".repeat(100);
+
+    let mut jar = CookieJar::new();
+    jar.set_compression(Compression::new());
+    jar.add(Cookie::new("data", value.clone()));
+
+    // The jar keeps the uncompressed value.
+    assert_eq!(jar.get("data").map(Cookie::value), Some(value.as_str()));
+
+    let header = jar.set_cookie_headers().next().unwrap().unwrap();
+    let compressed = header.strip_prefix("data=").unwrap();
+    assert!(Compression::is_compressed(compressed));
+
+    let mut jar = CookieJar::from_cookie(&header);
+    jar.set_compression(Compression::new());
+    assert_eq!(jar.get("data").map(Cookie::value), Some(value.as_str()));
+}
+
+#[test]
+fn jar_keeps_invalid_compressed_cookies() {
+    let mut jar = CookieJar::from_cookie("data=~zAAAA; pref=~zebra; theme=dark");
+    jar.set_compression(Compression::new());
+
+    assert_eq!(jar.get("data").map(Cookie::value), Some("~zAAAA"));
+    assert_eq!(jar.get("pref").map(Cookie::value), Some("~zebra"));
+    assert_eq!(jar.get("theme").map(Cookie::value), Some("dark"));
+
+    // Also for cookies that are added after compression is enabled.
+    jar.add_original(Cookie::new("other", "~zz"));
+    assert_eq!(jar.get("other").map(Cookie::value), Some("~zz"));
+}
+
+#[test]
+fn values_with_marker_are_always_compressed() {
+    let compression = Compression::new();
+
+    let compressed = compression.compress("~zz");
+    assert_ne!(compressed, "~zz");
+    assert_eq!(compression.decompress(&compressed).as_deref(), Ok("~zz"));
+
+    let mut jar = CookieJar::new();
+    jar.set_compression(compression);
+    jar.add(Cookie::new("data", "~zz"));
+
+    let header = jar.set_cookie_headers().next().unwrap().unwrap();
+    assert_ne!(header, "data=~zz");
+
+    let mut jar = CookieJar::from_cookie(&header);
+    jar.set_compression(compression);
+    assert_eq!(jar.get("data").map(Cookie::value), Some("~zz"));
+}
@@ -0,0 +1,234 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use cookie_monster::{Cookie, Expires};
+
+#[test]
+fn session_cookie() {
+    let now = SystemTime::now();
+    let cookie = Cookie::new("foo", "bar");
+
+    assert_eq!(cookie.effective_expiry(now), None);
+    assert!(!cookie.is_expired(now));
+    assert_eq!(cookie.remaining(now), None);
+}
+
+#[test]
+fn max_age() {
+    let now = SystemTime::now();
+    let cookie = Cookie::build("foo", "bar").max_age_secs(10).build();
+
+    assert_eq!(
+        cookie.effective_expiry(now),
+        Some(now + Duration::from_secs(10))
+    );
+    assert!(!cookie.is_expired(now));
+    assert!(!cookie.is_expired(now + Duration::from_secs(9)));
+    assert_eq!(cookie.remaining(now), Some(Duration::from_secs(10)));
+
+    let cookie = Cookie::build("foo", "bar").max_age_secs(0).build();
+    assert!(cookie.is_expired(now));
+    assert_eq!(cookie.remaining(now), Some(Duration::ZERO));
+
+    // Negative values also expire the cookie now.
+    let cookie = Cookie::build("foo", "bar").max_age_secs(-1).build();
+    assert_eq!(cookie.effective_expiry(now), Some(now));
+    assert!(cookie.is_expired(now));
+    assert_eq!(cookie.remaining(now), Some(Duration::ZERO));
+
+    // Too large to represent, but surely not expired.
+    let cookie = Cookie::build("foo", "bar").max_age_secs(i64::MAX).build();
+    assert!(!cookie.is_expired(now));
+}
+
+#[test]
+fn max_age_takes_precedence() {
+    let now = SystemTime::now();
+    let cookie = Cookie::build("foo", "bar")
+        .expires(Expires::remove())
+        .max_age_secs(60)
+        .build();
+
+    assert!(!cookie.is_expired(now));
+    assert_eq!(cookie.remaining(now), Some(Duration::from_secs(60)));
+
+    let cookie = Cookie::build("foo", "bar")
+        .expires(Expires::remove())
+        .build();
+    assert!(cookie.is_expired(now));
+    assert_eq!(cookie.remaining(now), Some(Duration::ZERO));
+    assert!(cookie.effective_expiry(now).unwrap() < now);
+}
+
+#[test]
+fn removed_cookie() {
+    let cookie = Cookie::remove("foo");
+    assert!(cookie.is_expired(UNIX_EPOCH));
+    assert!(cookie.is_expired(SystemTime::now()));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn expiry_time() {
+    use time::{Duration, macros::datetime};
+
+    let now = datetime!(2025-01-01 0:00 UTC);
+    let cookie = Cookie::build("foo", "bar")
+        .expires(datetime!(2025-01-02 0:00 UTC))
+        .build();
+
+    assert_eq!(
+        cookie.effective_expiry_time(now),
+        Some(datetime!(2025-01-02 0:00 UTC))
+    );
+    assert!(!cookie.is_expired_time(now));
+    assert!(cookie.is_expired_time(datetime!(2025-01-02 0:00 UTC)));
+    assert_eq!(cookie.remaining_time(now), Some(Duration::days(1)));
+    assert_eq!(
+        cookie.remaining_time(datetime!(2025-02-01 0:00 UTC)),
+        Some(Duration::ZERO)
+    );
+
+    let cookie = Cookie::build("foo", "bar")
+        .expires(datetime!(2025-01-02 0:00 UTC))
+        .max_age_secs(60)
+        .build();
+    assert_eq!(cookie.remaining_time(now), Some(Duration::minutes(1)));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn expiry_chrono() {
+    use chrono::{Duration, TimeZone, Utc};
+
+    let now = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let expires = Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap();
+    let cookie = Cookie::build("foo", "bar").expires(expires).build();
+
+    assert_eq!(cookie.effective_expiry_chrono(now), Some(expires));
+    assert!(!cookie.is_expired_chrono(now));
+    assert!(cookie.is_expired_chrono(expires));
+    assert_eq!(cookie.remaining_chrono(now), Some(Duration::days(1)));
+
+    let cookie = Cookie::build("foo", "bar")
+        .expires(expires)
+        .max_age_secs(0)
+        .build();
+    assert!(cookie.is_expired_chrono(now));
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn expiry_jiff() {
+    use jiff::{SignedDuration, civil::datetime, tz::TimeZone};
+
+    let now = datetime(2025, 1, 1, 0, 0, 0, 0)
+        .to_zoned(TimeZone::UTC)
+        .unwrap();
+    let expires = datetime(2025, 1, 2, 0, 0, 0, 0)
+        .to_zoned(TimeZone::UTC)
+        .unwrap();
+    let cookie = Cookie::build("foo", "bar").expires(expires.clone()).build();
+
+    assert_eq!(cookie.effective_expiry_jiff(&now), Some(expires.clone()));
+    assert!(!cookie.is_expired_jiff(&now));
+    assert!(cookie.is_expired_jiff(&expires));
+    assert_eq!(
+        cookie.remaining_jiff(&now),
+        Some(SignedDuration::from_hours(24))
+    );
+}
+
+#[test]
+fn system_time_conversions() {
+    let at = UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+    let expires = Expires::from(at);
+
+    assert_eq!(expires.unix_timestamp(), Some(1_445_412_480));
+    assert_eq!(expires.to_system_time(), Some(at));
+
+    // Sub-second precision is dropped.
+    let expires = Expires::from(at + Duration::from_millis(500));
+    assert_eq!(expires.to_system_time(), Some(at));
+
+    let before_epoch = Expires::from(UNIX_EPOCH - Duration::from_millis(500));
+    assert_eq!(before_epoch.unix_timestamp(), Some(-1));
+
+    assert_eq!(Expires::Session.to_system_time(), None);
+    assert_eq!(Expires::Remove.to_system_time(), Some(UNIX_EPOCH));
+    assert_eq!(Expires::Remove, Expires::from_unix_timestamp(0));
+
+    assert_eq!(
+        Expires::from_unix_timestamp(i64::MAX),
+        Expires::from_unix_timestamp(253_402_300_799)
+    );
+}
+
+#[cfg(all(feature = "time", feature = "chrono", feature = "jiff"))]
+#[test]
+fn cross_crate_conversions() {
+    use chrono::{TimeZone, Utc};
+    use time::macros::datetime;
+
+    let time = datetime!(2015-10-21 7:28:00 UTC);
+    let chrono = Utc.with_ymd_and_hms(2015, 10, 21, 7, 28, 0).unwrap();
+    let jiff = jiff::Timestamp::from_second(1_445_412_480).unwrap();
+
+    for cookie in [
+        Cookie::build("foo", "bar").expires(time).build(),
+        Cookie::build("foo", "bar").expires(chrono).build(),
+        Cookie::build("foo", "bar").expires(jiff).build(),
+        Cookie::build("foo", "bar")
+            .expires(jiff.in_tz("Europe/Amsterdam").unwrap())
+            .build(),
+        Cookie::build("foo", "bar")
+            .expires(UNIX_EPOCH + Duration::from_secs(1_445_412_480))
+            .build(),
+    ] {
+        assert_eq!(cookie.expires_time(), Some(time));
+        assert_eq!(cookie.expires_chrono(), Some(chrono));
+        assert_eq!(
+            cookie.expires_jiff().map(|zoned| zoned.timestamp()),
+            Some(jiff)
+        );
+        assert_eq!(
+            cookie.serialize().as_deref(),
+            Ok("foo=bar; Expires=Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+    }
+}
+
+#[test]
+fn fixed_clock() {
+    use cookie_monster::{Clock, CookieJar, FixedClock};
+
+    let clock = FixedClock::from_unix_timestamp(784_111_777);
+    assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(784_111_777));
+
+    let removed = Cookie::remove_with("foo", &clock);
+    assert!(removed.is_expired(clock.now()));
+    assert_eq!(
+        removed.serialize().as_deref(),
+        Ok("foo=; Max-Age=0; Expires=Sat, 06 Nov 1993 08:49:37 GMT")
+    );
+
+    let mut jar = CookieJar::from_cookie("foo=bar");
+    jar.set_clock(clock);
+    assert_eq!(jar.clock().now(), clock.now());
+
+    jar.remove(Cookie::build("foo", "").path("/"));
+    jar.clear();
+
+    let headers: Vec<_> = jar.set_cookie_headers().collect();
+    assert_eq!(
+        headers,
+        [Ok(
+            "foo=; Max-Age=0; Path=/; Expires=Sat, 06 Nov 1993 08:49:37 GMT".to_string()
+        )]
+    );
+
+    let cookie = Cookie::build("foo", "bar").max_age_secs(60).build();
+    assert_eq!(
+        cookie.remaining(jar.clock().now()),
+        Some(Duration::from_secs(60))
+    );
+}
@@ -1,4 +1,6 @@
-use cookie_monster::{Cookie, CookieJar};
+use cookie_monster::{
+    Consent, Cookie, CookieCategory, CookieJar, CookiePolicy, CookieTemplate, DeltaKind, SameSite,
+};
 
 #[test]
 fn basic_jar() {
@@ -95,3 +97,518 @@ fn basic_reassign() {
     let mut set_cookie = jar.set_cookie_headers();
     assert_eq!(set_cookie.next().unwrap().as_deref(), Ok("theme=val2"));
 }
+
+#[test]
+fn set_cookie_headers_are_insertion_ordered() {
+    let mut jar = CookieJar::from_cookie("a=1; b=2; c=3");
+
+    for name in ["z", "c", "m", "a", "q"] {
+        jar.add(Cookie::new(name, "new"));
+    }
+
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert_eq!(headers, ["z=new", "c=new", "m=new", "a=new", "q=new"]);
+}
+
+#[test]
+fn replacing_a_change_keeps_its_position() {
+    let mut jar = CookieJar::new();
+
+    jar.add(Cookie::new("a", "1"));
+    jar.add(Cookie::new("b", "1"));
+    jar.add(Cookie::new("a", "2"));
+
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert_eq!(headers, ["a=2", "b=1"]);
+}
+
+#[test]
+#[cfg(all(not(feature = "time"), not(feature = "chrono"), not(feature = "jiff")))]
+fn remove_and_replace_under_different_path() {
+    let mut jar = CookieJar::from_cookie("session=old");
+
+    let removed = jar.remove(Cookie::named("session").path("/old"));
+    assert_eq!(removed.as_ref().map(Cookie::value), Some("old"));
+    assert_eq!(jar.get("session"), None);
+
+    jar.add(Cookie::build("session", "new").path("/"));
+    assert_eq!(jar.get("session").map(Cookie::value), Some("new"));
+
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert_eq!(
+        headers,
+        [
+            "session=; Max-Age=0; Path=/old; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+            "session=new; Path=/"
+        ]
+    );
+}
+
+#[test]
+fn remove_replaces_new_cookie_with_same_path() {
+    let mut jar = CookieJar::new();
+
+    jar.add(Cookie::build("session", "abc").path("/"));
+    let removed = jar.remove(Cookie::named("session").path("/"));
+
+    assert_eq!(removed.as_ref().map(Cookie::value), Some("abc"));
+    assert_eq!(jar.get("session"), None);
+    assert_eq!(jar.set_cookie_headers().count(), 1);
+}
+
+#[test]
+fn remove_without_path_hides_new_cookies() {
+    let mut jar = CookieJar::new();
+
+    jar.add(Cookie::build("session", "abc").path("/"));
+    jar.add(Cookie::build("session", "def").path("/app"));
+    jar.add(Cookie::new("theme", "dark"));
+    let removed = jar.remove(Cookie::named("session"));
+
+    assert_eq!(removed.as_ref().map(Cookie::value), Some("def"));
+    assert_eq!(jar.get("session"), None);
+    assert_eq!(jar.iter().map(Cookie::name).collect::<Vec<_>>(), ["theme"]);
+
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers[0], "theme=dark");
+    assert!(headers[1].starts_with("session=; Max-Age=0; Expires="));
+}
+
+#[test]
+fn iter_lists_visible_cookies() {
+    let mut jar = CookieJar::from_cookie("a=1; b=2; c=3");
+
+    jar.remove(Cookie::named("b"));
+    jar.add(Cookie::new("c", "new"));
+    jar.add(Cookie::new("d", "4"));
+
+    let cookies: Vec<_> = jar.iter().map(|c| (c.name(), c.value())).collect();
+    assert_eq!(cookies, [("a", "1"), ("c", "new"), ("d", "4")]);
+
+    assert_eq!(jar.len(), 3);
+    assert!(!jar.is_empty());
+    assert!(jar.contains("a"));
+    assert!(!jar.contains("b"));
+
+    let owned: Vec<Cookie> = jar.into_iter().collect();
+    assert_eq!(owned.len(), 3);
+    assert_eq!(owned[1].value(), "new");
+}
+
+#[test]
+fn delta_lists_changes() {
+    let mut jar = CookieJar::from_cookie("a=1; b=2");
+
+    jar.add(Cookie::new("c", "3"));
+    jar.remove(Cookie::named("a"));
+
+    let delta: Vec<_> = jar.delta().map(|(kind, c)| (kind, c.name())).collect();
+    assert_eq!(delta, [(DeltaKind::New, "c"), (DeltaKind::Removal, "a")]);
+}
+
+#[test]
+fn retain_removes_cookies() {
+    let mut jar = CookieJar::from_cookie("keep=1; drop=2");
+    jar.add(Cookie::new("new", "3"));
+
+    jar.retain(|c| c.name() == "keep");
+
+    assert_eq!(jar.iter().map(Cookie::name).collect::<Vec<_>>(), ["keep"]);
+
+    let delta: Vec<_> = jar.delta().map(|(kind, c)| (kind, c.name())).collect();
+    assert_eq!(
+        delta,
+        [(DeltaKind::Removal, "new"), (DeltaKind::Removal, "drop")]
+    );
+}
+
+#[test]
+fn clear_and_reset_delta() {
+    let mut jar = CookieJar::from_cookie("a=1; b=2");
+    jar.add(Cookie::new("a", "changed"));
+
+    jar.clear();
+    assert!(jar.is_empty());
+    assert_eq!(jar.delta().count(), 2);
+    assert!(jar.delta().all(|(kind, _)| kind == DeltaKind::Removal));
+
+    jar.reset_delta();
+    assert_eq!(jar.get("a").map(Cookie::value), Some("1"));
+    assert_eq!(jar.len(), 2);
+    assert_eq!(jar.set_cookie_headers().count(), 0);
+}
+
+#[test]
+fn extend_and_collect() {
+    let mut jar: CookieJar = [Cookie::new("a", "1"), Cookie::new("b", "2")]
+        .into_iter()
+        .collect();
+
+    jar.extend([Cookie::build("c", "3")]);
+
+    assert_eq!(jar.len(), 3);
+    assert_eq!(jar.delta().count(), 3);
+    assert!(jar.delta().all(|(kind, _)| kind == DeltaKind::New));
+
+    let names: Vec<_> = (&jar).into_iter().map(Cookie::name).collect();
+    assert_eq!(names, ["a", "b", "c"]);
+}
+
+#[test]
+fn policy_defaults() {
+    let mut jar = CookieJar::new();
+    jar.set_policy(
+        CookiePolicy::new()
+            .path("/")
+            .domain("rust-lang.com")
+            .max_age_secs(60)
+            .secure(true)
+            .http_only(true)
+            .same_site(SameSite::Lax),
+    );
+
+    jar.add(Cookie::new("a", "1"));
+    jar.add(
+        Cookie::build("b", "2")
+            .path("/b")
+            .max_age_secs(10)
+            .set_secure(false)
+            .set_http_only(false)
+            .same_site(SameSite::Strict),
+    );
+
+    let headers: Vec<_> = jar.set_cookie_headers().collect();
+    assert_eq!(
+        headers,
+        [
+            Ok(
+                "a=1; Max-Age=60; Domain=rust-lang.com; Path=/; Secure; HttpOnly; SameSite=Lax"
+                    .to_string()
+            ),
+            Ok("b=2; Max-Age=10; Domain=rust-lang.com; Path=/b; SameSite=Strict".to_string()),
+        ]
+    );
+
+    // Originals are left untouched.
+    let mut jar = CookieJar::from_cookie("a=1");
+    jar.set_policy(CookiePolicy::new().path("/"));
+    assert_eq!(jar.get("a").and_then(Cookie::path), None);
+}
+
+#[test]
+fn policy_requirements() {
+    let mut jar = CookieJar::new();
+    jar.set_policy(
+        CookiePolicy::new()
+            .require_secure()
+            .require_http_only()
+            .require_same_site(SameSite::Lax),
+    );
+
+    jar.add(
+        Cookie::build("a", "1")
+            .set_secure(false)
+            .set_http_only(false)
+            .same_site(SameSite::None),
+    );
+    jar.add(Cookie::build("b", "2").same_site(SameSite::Strict));
+
+    let headers: Vec<_> = jar.set_cookie_headers().collect();
+    assert_eq!(
+        headers,
+        [
+            Ok("a=1; Secure; HttpOnly; SameSite=Lax".to_string()),
+            Ok("b=2; Secure; HttpOnly; SameSite=Strict".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn policy_applies_to_earlier_changes() {
+    let mut jar = CookieJar::new();
+    jar.add(Cookie::new("a", "1"));
+    jar.remove(Cookie::named("b"));
+    jar.set_policy(CookiePolicy::new().path("/").require_secure());
+
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert_eq!(headers[0], "a=1; Path=/; Secure");
+    assert!(headers[1].starts_with("b=; Max-Age=0; Path=/; Secure; Expires="));
+}
+
+#[test]
+fn policy_applies_to_removals() {
+    let mut jar = CookieJar::from_cookie("session=abc");
+    jar.set_policy(CookiePolicy::new().path("/").require_secure());
+
+    jar.add(Cookie::new("session", "def"));
+    let removed = jar.remove(Cookie::named("session"));
+    assert_eq!(
+        removed.map(|c| c.value().to_string()),
+        Some("def".to_string())
+    );
+
+    // The removal replaces the added cookie, since the default path is the same.
+    let delta: Vec<_> = jar.delta().collect();
+    assert_eq!(delta.len(), 1);
+    assert_eq!(delta[0].0, DeltaKind::Removal);
+    assert_eq!(delta[0].1.path(), Some("/"));
+    assert!(delta[0].1.is_secure());
+}
+
+#[test]
+fn dev_mode_hosts() {
+    let mut jar = CookieJar::new();
+
+    for host in [
+        "localhost",
+        "LOCALHOST:3000",
+        "app.localhost",
+        "localhost.",
+        "127.0.0.1",
+        "127.1.2.3:8080",
+        "::1",
+        "[::1]:8080",
+    ] {
+        assert!(jar.enable_dev_mode(host, false), "{host}");
+        assert!(!jar.enable_dev_mode(host, true), "{host}");
+    }
+
+    for host in [
+        "example.com",
+        "localhost.example.com",
+        "notlocalhost",
+        "10.0.0.1",
+        "[::2]:8080",
+        "",
+    ] {
+        assert!(!jar.enable_dev_mode(host, false), "{host}");
+    }
+    assert!(!jar.is_dev_mode());
+}
+
+#[test]
+fn dev_mode_strips_secure() {
+    let mut jar = CookieJar::new();
+    jar.add(Cookie::secure("a", "1"));
+    jar.add(
+        Cookie::build("b", "2")
+            .same_site(SameSite::None)
+            .partitioned(),
+    );
+    jar.remove(Cookie::host("c", ""));
+
+    assert!(jar.enable_dev_mode("localhost:8080", false));
+
+    let headers: Vec<_> = jar.set_cookie_headers().collect();
+    assert_eq!(headers[0].as_deref(), Ok("a=1"));
+    assert_eq!(headers[1].as_deref(), Ok("b=2; Partitioned; SameSite=None"));
+    assert!(
+        headers[2]
+            .as_deref()
+            .unwrap()
+            .starts_with("c=; Max-Age=0; Path=/; Expires=")
+    );
+
+    // The cookies in the jar keep their attributes.
+    assert!(jar.get("a").unwrap().is_secure());
+
+    jar.set_dev_mode(false);
+    assert_eq!(
+        jar.set_cookie_headers().next().unwrap().as_deref(),
+        Ok("__Secure-a=1; Secure")
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn dev_mode_for_request() {
+    use http::Request;
+
+    let request = Request::get("/")
+        .header("host", "localhost:3000")
+        .body(())
+        .unwrap();
+    let mut jar = CookieJar::new();
+    assert!(jar.enable_dev_mode_for_request(request.uri(), request.headers()));
+
+    let request = Request::get("/")
+        .header("host", "localhost:3000")
+        .header("x-forwarded-proto", "https")
+        .body(())
+        .unwrap();
+    assert!(!jar.enable_dev_mode_for_request(request.uri(), request.headers()));
+
+    let request = Request::get("http://[::1]:3000/").body(()).unwrap();
+    assert!(jar.enable_dev_mode_for_request(request.uri(), request.headers()));
+
+    let request = Request::get("https://localhost/").body(()).unwrap();
+    assert!(!jar.enable_dev_mode_for_request(request.uri(), request.headers()));
+
+    let request = Request::get("/")
+        .header("host", "example.com")
+        .body(())
+        .unwrap();
+    assert!(!jar.enable_dev_mode_for_request(request.uri(), request.headers()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn touch_sliding_session() {
+    use cookie_monster::FixedClock;
+    use std::time::Duration;
+
+    let ttl = Duration::from_secs(3600);
+    let threshold = Duration::from_secs(600);
+
+    let mut jar = CookieJar::from_cookie("session=abc; session~expires=4000; theme=dark");
+    jar.set_policy(CookiePolicy::new().path("/"));
+    jar.set_clock(FixedClock::from_unix_timestamp(3000));
+
+    assert!(!jar.touch("missing", ttl, threshold));
+    assert!(!jar.touch("session", ttl, threshold));
+    assert_eq!(jar.delta().count(), 0);
+
+    jar.set_clock(FixedClock::from_unix_timestamp(3500));
+    assert!(jar.touch("session", ttl, threshold));
+
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert_eq!(
+        headers,
+        [
+            "session=abc; Max-Age=3600; Path=/",
+            "session~expires=7100; Max-Age=3600; Path=/"
+        ]
+    );
+
+    // The re-issued cookie is fresh again.
+    assert!(!jar.touch("session", ttl, threshold));
+    assert_eq!(jar.delta().count(), 2);
+    assert_eq!(jar.get("session").map(Cookie::value), Some("abc"));
+    assert_eq!(jar.sliding_expiry("session"), Some(7100));
+
+    // Cookies without a companion cookie get one.
+    assert_eq!(jar.sliding_expiry("theme"), None);
+    assert!(jar.touch("theme", ttl, threshold));
+    assert_eq!(jar.get("theme").map(Cookie::value), Some("dark"));
+    assert_eq!(jar.sliding_expiry("theme"), Some(7100));
+
+    // The companion cookies are not visible, and are removed together with their cookie.
+    assert_eq!(jar.get("session~expires"), None);
+    assert_eq!(jar.len(), 2);
+    jar.remove(Cookie::named("session"));
+    assert_eq!(jar.sliding_expiry("session"), None);
+    assert_eq!(jar.len(), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn touch_keeps_prefix_requirements() {
+    use cookie_monster::FixedClock;
+    use std::time::Duration;
+
+    let mut jar = CookieJar::from_cookie("__Host-session=abc; __Secure-id=1");
+    jar.set_clock(FixedClock::from_unix_timestamp(0));
+
+    assert!(jar.touch("session", Duration::from_secs(60), Duration::ZERO));
+    assert!(jar.touch("id", Duration::from_secs(60), Duration::ZERO));
+
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert_eq!(
+        headers,
+        [
+            "__Host-session=abc; Max-Age=60; Path=/; Secure",
+            "__Host-session~expires=60; Max-Age=60; Path=/; Secure",
+            "__Secure-id=1; Max-Age=60; Secure",
+            "__Secure-id~expires=60; Max-Age=60; Secure"
+        ]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn touch_keeps_values_with_digit_suffixes() {
+    use cookie_monster::FixedClock;
+    use std::time::Duration;
+
+    let ttl = Duration::from_secs(3600);
+    let threshold = Duration::from_secs(600);
+
+    let mut jar = CookieJar::from_cookie("version=v1.2; token=abc.99999999999");
+    jar.set_clock(FixedClock::from_unix_timestamp(1000));
+
+    // The `.N` suffixes are part of the values, not expiry timestamps.
+    assert_eq!(jar.sliding_expiry("version"), None);
+    assert_eq!(jar.sliding_expiry("token"), None);
+
+    assert!(jar.touch("version", ttl, threshold));
+    assert!(jar.touch("token", ttl, threshold));
+
+    assert_eq!(jar.get("version").map(Cookie::value), Some("v1.2"));
+    assert_eq!(jar.get("token").map(Cookie::value), Some("abc.99999999999"));
+    assert_eq!(jar.sliding_expiry("version"), Some(4600));
+    assert_eq!(jar.sliding_expiry("token"), Some(4600));
+}
+
+#[test]
+fn consent_filters_set_cookie_headers() {
+    static GA: CookieTemplate = CookieTemplate::new("_ga").category(CookieCategory::Analytics);
+
+    // Without a consent cookie, nothing is filtered.
+    let mut jar = CookieJar::new();
+    jar.add(GA.with_value("GA1.1"));
+    assert_eq!(jar.consent(), None);
+    assert_eq!(
+        jar.set_cookie_headers().next().unwrap().as_deref(),
+        Ok("_ga=GA1.1")
+    );
+
+    // A missing consent cookie only allows necessary cookies.
+    jar.set_consent_cookie("consent");
+    assert_eq!(jar.consent(), Some(Consent::none()));
+    jar.add(Cookie::new("session", "abc"));
+
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert!(headers[0].starts_with("_ga=; Max-Age=0; Expires="));
+    assert_eq!(headers[1], "session=abc");
+
+    // The removal keeps the Path of the cookie, so the user-agent removes it.
+    jar.add(
+        Cookie::build("theme", "dark")
+            .path("/app")
+            .category(CookieCategory::Preferences),
+    );
+    let theme = jar.set_cookie_headers().nth(2).unwrap().unwrap();
+    assert!(theme.starts_with("theme=; Max-Age=0; Path=/app; Expires="));
+
+    // Consent given in this request takes effect immediately.
+    jar.add(Cookie::new("consent", Consent::all().to_string()));
+    assert_eq!(jar.consent(), Some(Consent::all()));
+    let headers: Vec<_> = jar.set_cookie_headers().map(Result::unwrap).collect();
+    assert_eq!(
+        headers,
+        [
+            "_ga=GA1.1",
+            "session=abc",
+            "theme=dark; Path=/app",
+            "consent=preferences|analytics|marketing"
+        ]
+    );
+
+    // Parsed consent cookies.
+    let mut jar = CookieJar::from_cookie("consent=marketing|unknown");
+    jar.set_consent_cookie("consent");
+    jar.add(GA.with_value("GA1.1"));
+    assert_eq!(
+        jar.consent(),
+        Some(Consent::none().with(CookieCategory::Marketing))
+    );
+    assert!(jar.get("_ga").is_some());
+    assert!(
+        jar.set_cookie_headers()
+            .next()
+            .unwrap()
+            .unwrap()
+            .starts_with("_ga=; Max-Age=0")
+    );
+}
@@ -0,0 +1,72 @@
+use cookie_monster::{Cookie, CookieJar, NonUtf8, ParseOptions};
+
+const HEADER: &[u8] = b"session=abc; tracker=caf\xe9; theme=dark";
+
+fn values(jar: &CookieJar) -> Vec<(&str, &str)> {
+    jar.iter().map(|c| (c.name(), c.value())).collect()
+}
+
+#[test]
+fn invalid_utf8_is_isolated() {
+    let jar = CookieJar::from_cookie_bytes(HEADER, &ParseOptions::new());
+    assert_eq!(values(&jar), [("session", "abc"), ("theme", "dark")]);
+
+    let jar = CookieJar::from_cookie_bytes(b"\xff=a; b=\xfe", &ParseOptions::new());
+    assert!(jar.is_empty());
+}
+
+#[test]
+fn invalid_utf8_latin1() {
+    let options = ParseOptions::new().non_utf8(NonUtf8::Latin1);
+    let jar = CookieJar::from_cookie_bytes(HEADER, &options);
+
+    assert_eq!(
+        values(&jar),
+        [("session", "abc"), ("tracker", "café"), ("theme", "dark")]
+    );
+}
+
+#[test]
+fn invalid_utf8_lossy() {
+    let options = ParseOptions::new().non_utf8(NonUtf8::LossyUtf8);
+    let jar = CookieJar::from_cookie_bytes(HEADER, &options);
+
+    assert_eq!(
+        values(&jar),
+        [
+            ("session", "abc"),
+            ("tracker", "caf\u{FFFD}"),
+            ("theme", "dark")
+        ]
+    );
+}
+
+#[test]
+fn valid_utf8() {
+    let jar = CookieJar::from_cookie_bytes("a=1; b=é".as_bytes(), &ParseOptions::new());
+    assert_eq!(values(&jar), [("a", "1"), ("b", "é")]);
+    assert_eq!(jar.get("b"), Some(&Cookie::new("b", "é")));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_headers() {
+    use http::{HeaderMap, HeaderValue};
+
+    let mut headers = HeaderMap::new();
+    headers.append("cookie", HeaderValue::from_bytes(HEADER).unwrap());
+    headers.append("cookie", HeaderValue::from_static("other=1"));
+
+    let jar = CookieJar::from_headers(&headers);
+    assert_eq!(
+        values(&jar),
+        [("session", "abc"), ("theme", "dark"), ("other", "1")]
+    );
+
+    let jar = CookieJar::from_headers_raw(&headers);
+    assert_eq!(jar.len(), 3);
+
+    let options = ParseOptions::new().non_utf8(NonUtf8::Latin1);
+    let jar = CookieJar::from_headers_with_options(&headers, &options);
+    assert_eq!(jar.get("tracker").map(Cookie::value), Some("café"));
+}
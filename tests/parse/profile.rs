@@ -0,0 +1,88 @@
+use cookie_monster::{Cookie, CookieJar, ErrorKind, ParseOptions, ParseProfile};
+
+fn parse(string: &str, profile: ParseProfile) -> Result<(String, String), ErrorKind> {
+    Cookie::parse_cookie_with_options(string, &ParseOptions::new().profile(profile))
+        .map(|c| (c.name().to_string(), c.value().to_string()))
+        .map_err(|e| e.kind())
+}
+
+fn ok(name: &str, value: &str) -> Result<(String, String), ErrorKind> {
+    Ok((name.to_string(), value.to_string()))
+}
+
+#[test]
+fn lenient() {
+    use ParseProfile::Lenient;
+
+    assert_eq!(parse("foo=bar", Lenient), ok("foo", "bar"));
+    assert_eq!(parse("föo=bär", Lenient), ok("föo", "bär"));
+    assert_eq!(parse("foo=a b", Lenient), Err(ErrorKind::InvalidValue(' ')));
+    assert_eq!(parse("foo=a,b", Lenient), Err(ErrorKind::InvalidValue(',')));
+    assert_eq!(parse("=bar", Lenient), Err(ErrorKind::NameEmpty));
+    assert_eq!(parse("bar", Lenient), Err(ErrorKind::EqualsNotFound));
+}
+
+#[test]
+fn default_is_lenient() {
+    let cookie = Cookie::parse_cookie("föo=bär").unwrap();
+    assert_eq!((cookie.name(), cookie.value()), ("föo", "bär"));
+}
+
+#[test]
+fn strict() {
+    use ParseProfile::Strict;
+
+    assert_eq!(parse("foo=bar", Strict), ok("foo", "bar"));
+    assert_eq!(parse("foo=\"bar\"", Strict), ok("foo", "bar"));
+    assert_eq!(parse("föo=bar", Strict), Err(ErrorKind::InvalidName('ö')));
+    assert_eq!(parse("foo=bär", Strict), Err(ErrorKind::InvalidValue('ä')));
+    assert_eq!(parse("foo=a b", Strict), Err(ErrorKind::InvalidValue(' ')));
+    assert_eq!(parse("foo=a,b", Strict), Err(ErrorKind::InvalidValue(',')));
+    assert_eq!(parse("=bar", Strict), Err(ErrorKind::NameEmpty));
+    assert_eq!(parse("bar", Strict), Err(ErrorKind::EqualsNotFound));
+}
+
+#[test]
+fn rfc6265bis() {
+    use ParseProfile::Rfc6265bis;
+
+    assert_eq!(parse("föo=bär", Rfc6265bis), ok("föo", "bär"));
+    assert_eq!(parse("foo=a b, \"c\"", Rfc6265bis), ok("foo", "a b, \"c\""));
+    assert_eq!(parse("foo=a\tb", Rfc6265bis), ok("foo", "a\tb"));
+    assert_eq!(parse("f(o)o=bar", Rfc6265bis), ok("f(o)o", "bar"));
+    assert_eq!(
+        parse("foo=a\0b", Rfc6265bis),
+        Err(ErrorKind::InvalidValue('\0'))
+    );
+    assert_eq!(parse("=abc", Rfc6265bis), ok("", "abc"));
+    assert_eq!(parse("abc", Rfc6265bis), ok("", "abc"));
+    assert_eq!(parse("=", Rfc6265bis), Err(ErrorKind::NameEmpty));
+    assert_eq!(parse(" ", Rfc6265bis), Err(ErrorKind::EqualsNotFound));
+}
+
+#[test]
+fn browser() {
+    use ParseProfile::Browser;
+
+    assert_eq!(parse("foo=a b", Browser), ok("foo", "a b"));
+    assert_eq!(parse("=bar", Browser), ok("", "bar"));
+    assert_eq!(parse("bar", Browser), ok("", "bar"));
+    assert_eq!(parse(" ", Browser), Err(ErrorKind::EqualsNotFound));
+    assert_eq!(
+        parse("foo=a\rb", Browser),
+        Err(ErrorKind::InvalidValue('\r'))
+    );
+}
+
+#[test]
+fn jar_with_profile() {
+    let header = "session=abc; prefs=dark mode; =nameless";
+
+    let jar = CookieJar::from_cookie(header);
+    assert_eq!(jar.len(), 1);
+
+    let options = ParseOptions::new().profile(ParseProfile::Browser);
+    let jar = CookieJar::from_cookie_with_options(header, &options);
+    assert_eq!(jar.get("prefs").map(Cookie::value), Some("dark mode"));
+    assert_eq!(jar.get("").map(Cookie::value), Some("nameless"));
+}
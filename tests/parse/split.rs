@@ -0,0 +1,92 @@
+use cookie_monster::{Cookie, ErrorKind};
+
+fn parse(header: &str) -> Vec<Result<(String, String), ErrorKind>> {
+    Cookie::split_parse(header)
+        .map(|cookie| {
+            cookie
+                .map(|c| (c.name().to_string(), c.value().to_string()))
+                .map_err(|e| e.kind())
+        })
+        .collect()
+}
+
+fn ok(name: &str, value: &str) -> Result<(String, String), ErrorKind> {
+    Ok((name.to_string(), value.to_string()))
+}
+
+fn borrows_from(header: &str, s: &str) -> bool {
+    let range = header.as_bytes().as_ptr_range();
+    range.contains(&s.as_ptr())
+}
+
+#[test]
+fn split_parse() {
+    assert_eq!(
+        parse("foo=bar; baz=qux;  a=\"b\" ;"),
+        vec![ok("foo", "bar"), ok("baz", "qux"), ok("a", "b")]
+    );
+
+    assert_eq!(parse(""), vec![]);
+    assert_eq!(parse(" ; ;"), vec![]);
+}
+
+#[test]
+fn split_parse_isolates_errors() {
+    assert_eq!(
+        parse("foo=bar; invalid; =empty; baz=qux"),
+        vec![
+            ok("foo", "bar"),
+            Err(ErrorKind::EqualsNotFound),
+            Err(ErrorKind::NameEmpty),
+            ok("baz", "qux")
+        ]
+    );
+}
+
+#[test]
+fn split_parse_borrows() {
+    let header = String::from("foo=bar; __Secure-id=abc");
+
+    for cookie in Cookie::split_parse(&header) {
+        let cookie = cookie.unwrap();
+        assert!(borrows_from(&header, cookie.name()));
+        assert!(borrows_from(&header, cookie.value()));
+    }
+}
+
+#[test]
+fn split_parse_into_owned() {
+    let cookies: Vec<Cookie> = Cookie::split_parse("foo=bar; __Host-id=abc")
+        .map(|cookie| cookie.unwrap().into_owned())
+        .collect();
+
+    assert_eq!(cookies[0], Cookie::new("foo", "bar"));
+    assert_eq!(cookies[1], Cookie::parse_cookie("__Host-id=abc").unwrap());
+    assert_eq!(cookies[1].serialize().as_deref(), Ok("__Host-id=abc"));
+}
+
+#[cfg(feature = "percent-encode")]
+#[test]
+fn split_parse_encoded() {
+    let header = String::from("foo=bar; baz=a%20b");
+    let mut cookies = Cookie::split_parse_encoded(&header);
+
+    let foo = cookies.next().unwrap().unwrap();
+    assert_eq!(foo.value(), "bar");
+    assert!(borrows_from(&header, foo.value()));
+
+    let baz = cookies.next().unwrap().unwrap();
+    assert_eq!(baz.value(), "a b");
+    assert!(!borrows_from(&header, baz.value()));
+}
+
+#[test]
+fn split_parse_error_offset() {
+    let header = "foo=bar; baz=q\0x";
+    let error = Cookie::split_parse(header).find_map(Result::err).unwrap();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidValue('\0'));
+    assert_eq!(error.name(), Some("baz"));
+    assert_eq!(error.offset(), Some(14));
+    assert_eq!(&header[14..15], "\0");
+}
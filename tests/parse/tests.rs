@@ -1,7 +1,11 @@
-use cookie_monster::{Cookie, Error};
+use cookie_monster::{Cookie, ErrorKind, Phase};
 
 pub mod util;
 
+mod bytes;
+mod profile;
+mod split;
+
 #[cfg(feature = "percent-encode")]
 mod encoded;
 
@@ -31,33 +35,33 @@ fn name_value() {
 
 #[test]
 fn invalid_name_value() {
-    assert_eq_parse!("foobar", Err(Error::EqualsNotFound));
-    assert_eq_parse!("foo&bar", Err(Error::EqualsNotFound));
+    assert_eq_parse!("foobar", Err(ErrorKind::EqualsNotFound));
+    assert_eq_parse!("foo&bar", Err(ErrorKind::EqualsNotFound));
 
-    assert_eq_parse!("", Err(Error::EqualsNotFound));
+    assert_eq_parse!("", Err(ErrorKind::EqualsNotFound));
 
-    assert_eq_parse!("=bar", Err(Error::NameEmpty));
-    assert_eq_parse!(" =bar", Err(Error::NameEmpty));
-    assert_eq_parse!("foo=\0", Err(Error::InvalidValue('\0')));
-    assert_eq_parse!("foo=test\0test", Err(Error::InvalidValue('\0')));
+    assert_eq_parse!("=bar", Err(ErrorKind::NameEmpty));
+    assert_eq_parse!(" =bar", Err(ErrorKind::NameEmpty));
+    assert_eq_parse!("foo=\0", Err(ErrorKind::InvalidValue('\0')));
+    assert_eq_parse!("foo=test\0test", Err(ErrorKind::InvalidValue('\0')));
 }
 
 #[test]
 fn name_value_brackets_spaces() {
     assert_eq_parse!("foo=\"bar\"", Ok(Cookie::new("foo", "bar")));
 
-    assert_eq_parse!("foo=\"  bar  \"", Err(Error::InvalidValue(' ')));
-
-    assert_eq_parse!("foo=\"", Err(Error::InvalidValue('\"')));
-    assert_eq_parse!("foo=\"\"bar\"\"", Err(Error::InvalidValue('\"')));
-    assert_eq_parse!("foo=\"bar", Err(Error::InvalidValue('\"')));
-    assert_eq_parse!("foo=\"\"bar", Err(Error::InvalidValue('\"')));
-    assert_eq_parse!("foo=bar\"", Err(Error::InvalidValue('\"')));
-    assert_eq_parse!("foo=bar\"\"", Err(Error::InvalidValue('\"')));
-    assert_eq_parse!("foo=\"bar\"\"", Err(Error::InvalidValue('\"')));
-    assert_eq_parse!("foo=\"  bar\"\"", Err(Error::InvalidValue(' ')));
-    assert_eq_parse!("foo=\"  bar\"  \"  ", Err(Error::InvalidValue(' ')));
-    assert_eq_parse!(" foo=\"bar   \" ", Err(Error::InvalidValue(' ')));
+    assert_eq_parse!("foo=\"  bar  \"", Err(ErrorKind::InvalidValue(' ')));
+
+    assert_eq_parse!("foo=\"", Err(ErrorKind::InvalidValue('\"')));
+    assert_eq_parse!("foo=\"\"bar\"\"", Err(ErrorKind::InvalidValue('\"')));
+    assert_eq_parse!("foo=\"bar", Err(ErrorKind::InvalidValue('\"')));
+    assert_eq_parse!("foo=\"\"bar", Err(ErrorKind::InvalidValue('\"')));
+    assert_eq_parse!("foo=bar\"", Err(ErrorKind::InvalidValue('\"')));
+    assert_eq_parse!("foo=bar\"\"", Err(ErrorKind::InvalidValue('\"')));
+    assert_eq_parse!("foo=\"bar\"\"", Err(ErrorKind::InvalidValue('\"')));
+    assert_eq_parse!("foo=\"  bar\"\"", Err(ErrorKind::InvalidValue(' ')));
+    assert_eq_parse!("foo=\"  bar\"  \"  ", Err(ErrorKind::InvalidValue(' ')));
+    assert_eq_parse!(" foo=\"bar   \" ", Err(ErrorKind::InvalidValue(' ')));
 }
 
 #[test]
@@ -68,5 +72,36 @@ fn name_value_brackets_spaces_not_eq() {
 #[test]
 fn invalid() {
     assert_eq_parse!("foo=bar)", Ok(Cookie::new("foo", "bar)")));
-    assert_eq_parse!("foo)=bar", Err(Error::InvalidName(')')));
+    assert_eq_parse!("foo)=bar", Err(ErrorKind::InvalidName(')')));
+}
+
+#[test]
+fn error_context() {
+    let error = Cookie::parse_cookie("foo)=bar").unwrap_err();
+    assert_eq!(error.phase(), Phase::Parse);
+    assert_eq!(error.name(), Some("foo)"));
+    assert_eq!(error.offset(), Some(3));
+
+    let error = Cookie::parse_cookie(" foo=b\"ar").unwrap_err();
+    assert_eq!(error.name(), Some("foo"));
+    assert_eq!(error.offset(), Some(6));
+
+    assert_eq!(
+        error.to_string(),
+        "The cookie value contains an invalid character: \" while parsing cookie `foo` at byte 6"
+    );
+}
+
+#[test]
+fn quoted() {
+    let cookie = Cookie::parse_cookie("foo=\"bar\"").unwrap();
+    assert!(cookie.is_quoted());
+    assert_eq!(cookie.value(), "bar");
+
+    assert!(!Cookie::parse_cookie("foo=bar").unwrap().is_quoted());
+
+    let cookie = Cookie::split_parse("a=\"1\"; b=2")
+        .map(|c| c.unwrap().is_quoted())
+        .collect::<Vec<_>>();
+    assert_eq!(cookie, [true, false]);
 }
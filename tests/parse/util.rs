@@ -1,7 +1,11 @@
 #[macro_export]
 macro_rules! assert_eq_parse {
     ($string:expr, $cookie:expr) => {
-        assert_eq!(Cookie::parse_cookie($string), $cookie, "parse went wrong");
+        assert_eq!(
+            Cookie::parse_cookie($string).map_err(|e| e.kind()),
+            $cookie,
+            "parse went wrong"
+        );
     };
 }
 
@@ -9,7 +13,7 @@ macro_rules! assert_eq_parse {
 macro_rules! assert_eq_parse_enc {
     ($string:expr, $cookie:expr) => {
         assert_eq!(
-            Cookie::parse_cookie_encoded($string),
+            Cookie::parse_cookie_encoded($string).map_err(|e| e.kind()),
             $cookie,
             "parse went wrong"
         );
@@ -19,6 +23,10 @@ macro_rules! assert_eq_parse_enc {
 #[macro_export]
 macro_rules! assert_ne_parse {
     ($string:expr, $cookie:expr) => {
-        assert_ne!(Cookie::parse_cookie($string), $cookie, "parse went wrong");
+        assert_ne!(
+            Cookie::parse_cookie($string).map_err(|e| e.kind()),
+            $cookie,
+            "parse went wrong"
+        );
     };
 }
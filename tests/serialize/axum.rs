@@ -1,5 +1,18 @@
-use axum::{Router, response::IntoResponse, routing::get};
-use cookie_monster::{Cookie, CookieJar};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use cookie_monster::{
+    Cookie, CookieJar, ErrorKind, SetCookieError, SetCookieErrorLayer, SetCookieErrorPolicy,
+};
+use tower_service::Service;
+
+use crate::shared::{extract_jar, ready};
 
 #[test]
 fn extract_axum() {
@@ -23,3 +36,138 @@ async fn handler(mut jar: CookieJar) -> impl IntoResponse {
 
     jar
 }
+
+fn jar_with_invalid_cookie() -> CookieJar {
+    let mut jar = CookieJar::new();
+    jar.add(Cookie::new("theme", "dark"));
+    jar.add(Cookie::build("session", "abc").path("invalid"));
+    jar
+}
+
+#[test]
+fn invalid_cookie_is_dropped_by_default() {
+    let response = jar_with_invalid_cookie().into_response();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get_all("set-cookie").iter().count(), 1);
+}
+
+// Sends a request to `/` through the error layer.
+fn send_with_policy<H, T>(handler: H, policy: SetCookieErrorPolicy) -> Response
+where
+    H: axum::handler::Handler<T, ()>,
+    T: 'static,
+{
+    let mut app = Router::new()
+        .route("/", get(handler))
+        .layer(SetCookieErrorLayer::new(policy));
+
+    let request = Request::get("/").body(Body::empty()).unwrap();
+    let Ok(response) = ready(app.call(request));
+    response
+}
+
+#[test]
+fn invalid_cookie_calls_hook() {
+    static CALLED: AtomicUsize = AtomicUsize::new(0);
+
+    fn hook(error: &SetCookieError) {
+        assert_eq!(error.name(), "session");
+        assert_eq!(error.error().kind(), ErrorKind::NoLeadingSlash);
+        CALLED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let response = send_with_policy(
+        async || jar_with_invalid_cookie(),
+        SetCookieErrorPolicy::Hook(hook),
+    );
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get_all("set-cookie").iter().count(), 1);
+    assert_eq!(CALLED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn invalid_cookie_rejects_response() {
+    let response = send_with_policy(
+        async || jar_with_invalid_cookie(),
+        SetCookieErrorPolicy::Reject,
+    );
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.headers().get_all("set-cookie").iter().count(), 0);
+}
+
+#[test]
+fn invalid_single_cookie() {
+    fn invalid_cookie() -> Cookie {
+        Cookie::build("session", "abc").path("invalid").build()
+    }
+
+    let response = invalid_cookie().into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get_all("set-cookie").iter().count(), 0);
+
+    let response = send_with_policy(async || invalid_cookie(), SetCookieErrorPolicy::Reject);
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[test]
+fn errors_of_all_response_parts_are_collected() {
+    static CALLED: AtomicUsize = AtomicUsize::new(0);
+
+    fn hook(_: &SetCookieError) {
+        CALLED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let response = send_with_policy(
+        async || {
+            let cookie = Cookie::build("theme", "dark").path("invalid").build();
+            (cookie, jar_with_invalid_cookie())
+        },
+        SetCookieErrorPolicy::Hook(hook),
+    );
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(CALLED.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn policy_extension() {
+    use cookie_monster::CookiePolicy;
+
+    let request = Request::builder()
+        .extension(CookiePolicy::new().path("/").require_secure())
+        .body(())
+        .unwrap();
+    let mut jar = extract_jar(request);
+
+    jar.add(Cookie::new("session", "abc"));
+    let response = jar.into_response();
+
+    let set_cookie: Vec<_> = response.headers().get_all("set-cookie").iter().collect();
+    assert_eq!(set_cookie, ["session=abc; Path=/; Secure"]);
+}
+
+#[test]
+fn dev_mode_extension() {
+    use cookie_monster::DevMode;
+
+    // The Host header doesn't decide if dev mode is enabled, only the extension does.
+    for (dev_mode, expected) in [
+        (Some(DevMode), "session=abc; Path=/"),
+        (None, "__Host-session=abc; Path=/; Secure"),
+    ] {
+        let mut request = Request::builder().header("host", "localhost:3000");
+        if let Some(dev_mode) = dev_mode {
+            request = request.extension(dev_mode);
+        }
+        let mut jar = extract_jar(request.body(()).unwrap());
+
+        jar.add(Cookie::host("session", "abc"));
+        let response = jar.into_response();
+
+        let set_cookie: Vec<_> = response.headers().get_all("set-cookie").iter().collect();
+        assert_eq!(set_cookie, [expected]);
+    }
+}
@@ -11,3 +11,18 @@ fn encoded() {
         Ok("foo%20!%%3F%3D=bar%3B%3B%2C%20a")
     );
 }
+
+#[test]
+fn encoded_quoted() {
+    assert_eq_ser_enc!(
+        Cookie::build("foo", "a b").quoted().build(),
+        Ok("foo=\"a%20b\"")
+    );
+
+    let mut cookie = Cookie::parse_cookie_encoded("foo=\"a%20b\"").unwrap();
+    assert!(cookie.is_quoted());
+    assert_eq!(cookie.value(), "a b");
+    assert_eq_ser_enc!(cookie, Ok("foo=a%20b"));
+    cookie.set_quoted(true);
+    assert_eq_ser_enc!(cookie, Ok("foo=\"a%20b\""));
+}
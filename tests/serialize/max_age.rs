@@ -17,3 +17,108 @@ fn max_age() {
         Ok("foo=bar; Max-Age=12")
     );
 }
+
+#[test]
+fn max_age_negative() {
+    use cookie_monster::MaxAge;
+
+    assert_eq_ser!(
+        Cookie::build("foo", "bar")
+            .max_age(MaxAge::from_secs(-5))
+            .build(),
+        Ok("foo=bar; Max-Age=-5")
+    );
+
+    let cookie = Cookie::build("foo", "bar").max_age_secs(-5).build();
+    assert_eq!(cookie.max_age_secs(), Some(-5));
+    assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn max_age_time() {
+    assert_eq_ser!(
+        Cookie::build("foo", "bar")
+            .max_age(time::Duration::minutes(2))
+            .build(),
+        Ok("foo=bar; Max-Age=120")
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn max_age_chrono() {
+    assert_eq_ser!(
+        Cookie::build("foo", "bar")
+            .max_age(chrono::TimeDelta::hours(1))
+            .build(),
+        Ok("foo=bar; Max-Age=3600")
+    );
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn max_age_jiff() {
+    use cookie_monster::MaxAge;
+    use jiff::{SignedDuration, ToSpan};
+
+    assert_eq_ser!(
+        Cookie::build("foo", "bar")
+            .max_age(SignedDuration::from_mins(1))
+            .build(),
+        Ok("foo=bar; Max-Age=60")
+    );
+    assert_eq_ser!(
+        Cookie::build("foo", "bar")
+            .max_age(MaxAge::try_from(1.week().days(1).hours(1)).unwrap())
+            .build(),
+        Ok("foo=bar; Max-Age=694800")
+    );
+
+    // The length of a month depends on the date, the clock of the builder is not known here.
+    assert!(MaxAge::try_from(1.month()).is_err());
+    assert!(MaxAge::try_from(1.year()).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn expires_in() {
+    use cookie_monster::FixedClock;
+
+    assert_eq_ser!(
+        Cookie::build("foo", "bar")
+            .clock(FixedClock::from_unix_timestamp(0))
+            .expires_in(Duration::from_secs(86_400))
+            .build(),
+        Ok("foo=bar; Expires=Fri, 02 Jan 1970 00:00:00 GMT")
+    );
+
+    // The clock may also be set afterwards.
+    assert_eq_ser!(
+        Cookie::build("foo", "bar")
+            .expires_in(Duration::from_secs(86_400))
+            .clock(FixedClock::from_unix_timestamp(0))
+            .build(),
+        Ok("foo=bar; Expires=Fri, 02 Jan 1970 00:00:00 GMT")
+    );
+
+    let cookie = Cookie::build("foo", "bar")
+        .expires_in(Duration::from_secs(60))
+        .build();
+    let remaining = cookie.remaining(std::time::SystemTime::now()).unwrap();
+    assert!(remaining <= Duration::from_secs(60));
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn expires_in_negative() {
+    use cookie_monster::FixedClock;
+
+    assert_eq_ser!(
+        Cookie::build("foo", "bar")
+            .clock(FixedClock::from_unix_timestamp(86_400))
+            .expires_in(jiff::SignedDuration::from_hours(-24))
+            .build(),
+        Ok("foo=bar; Expires=Thu, 01 Jan 1970 00:00:00 GMT")
+    );
+}
@@ -1,4 +1,4 @@
-use cookie_monster::{Cookie, Error};
+use cookie_monster::{Attribute, Cookie, ErrorKind, Phase};
 
 use crate::assert_eq_ser;
 
@@ -11,9 +11,64 @@ fn name_value() {
 
 #[test]
 fn invalid_name_value() {
-    assert_eq_ser!(Cookie::new("", "bar"), Err(&Error::NameEmpty));
-    assert_eq_ser!(Cookie::new("foo\0", "bar"), Err(&Error::InvalidName('\0')));
-    assert_eq_ser!(Cookie::new("foo", "bar\0"), Err(&Error::InvalidValue('\0')));
-    assert_eq_ser!(Cookie::new("foo", " "), Err(&Error::InvalidValue(' ')));
-    assert_eq_ser!(Cookie::new("foo", "\""), Err(&Error::InvalidValue('\"')));
+    assert_eq_ser!(Cookie::new("", "bar"), Err(ErrorKind::NameEmpty));
+    assert_eq_ser!(
+        Cookie::new("foo\0", "bar"),
+        Err(ErrorKind::InvalidName('\0'))
+    );
+    assert_eq_ser!(
+        Cookie::new("foo", "bar\0"),
+        Err(ErrorKind::InvalidValue('\0'))
+    );
+    assert_eq_ser!(Cookie::new("foo", " "), Err(ErrorKind::InvalidValue(' ')));
+    assert_eq_ser!(Cookie::new("foo", "\""), Err(ErrorKind::InvalidValue('\"')));
+}
+
+#[test]
+fn error_context() {
+    let error = Cookie::new("foo", "ba r").serialize().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidValue(' '));
+    assert_eq!(error.phase(), Phase::Serialize(Attribute::Value));
+    assert_eq!(error.name(), Some("foo"));
+    assert_eq!(error.offset(), Some(2));
+    assert_eq!(
+        error.to_string(),
+        "The cookie value contains an invalid character: \u{20} while serializing the value of cookie `foo` at byte 2"
+    );
+
+    let error = Cookie::build("foo", "bar")
+        .path("no-slash")
+        .build()
+        .serialize()
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::NoLeadingSlash);
+    assert_eq!(error.phase(), Phase::Serialize(Attribute::Path));
+    assert_eq!(error.name(), Some("foo"));
+    assert_eq!(
+        error.to_string(),
+        "The path attribute does not start with a leading slash while serializing the Path attribute of cookie `foo` at byte 0"
+    );
+}
+
+#[test]
+fn quoted() {
+    assert_eq_ser!(
+        Cookie::build("foo", "bar").quoted().build(),
+        Ok("foo=\"bar\"")
+    );
+    assert_eq_ser!(Cookie::build("foo", "").quoted().build(), Ok("foo=\"\""));
+    assert_eq_ser!(
+        Cookie::build("foo", "\"bar\"").quoted().build(),
+        Err(ErrorKind::InvalidValue('"'))
+    );
+
+    // Parsed quotes are only written back when re-quoting is enabled.
+    let mut cookie = Cookie::parse_cookie("foo=\"bar\"").unwrap();
+    assert!(cookie.is_quoted());
+    assert_eq_ser!(cookie, Ok("foo=bar"));
+    cookie.set_quoted(true);
+    assert_eq_ser!(cookie, Ok("foo=\"bar\""));
+    cookie.set_quoted(false);
+    assert!(!cookie.is_quoted());
+    assert_eq_ser!(cookie, Ok("foo=bar"));
 }
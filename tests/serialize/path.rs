@@ -1,4 +1,4 @@
-use cookie_monster::{Cookie, Error};
+use cookie_monster::{Cookie, ErrorKind};
 
 use crate::assert_eq_ser;
 
@@ -11,10 +11,10 @@ fn path() {
 
     assert_eq_ser!(
         Cookie::build("foo", "bar").path("home").build(),
-        Err(&Error::NoLeadingSlash)
+        Err(ErrorKind::NoLeadingSlash)
     );
     assert_eq_ser!(
         Cookie::build("foo", "bar").path("").build(),
-        Err(&Error::EmptyPathValue)
+        Err(ErrorKind::EmptyPathValue)
     );
 }
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use cookie_monster::{
+    Attribute, Base64UrlCodec, Cookie, CookieTemplate, ErrorKind, Phase, SameSite,
+};
+
+use crate::assert_eq_ser;
+
+static SESSION: CookieTemplate = CookieTemplate::new("session")
+    .path("/")
+    .domain("rust-lang.com")
+    .max_age(Duration::from_secs(3600))
+    .http_only()
+    .same_site(SameSite::Strict);
+
+#[test]
+fn template() {
+    assert_eq!(SESSION.name(), "session");
+
+    assert_eq_ser!(
+        SESSION.with_value("abc"),
+        Ok("session=abc; Max-Age=3600; Domain=rust-lang.com; Path=/; HttpOnly; SameSite=Strict")
+    );
+
+    let id = String::from("def");
+    assert_eq_ser!(
+        SESSION.with_value(id),
+        Ok("session=def; Max-Age=3600; Domain=rust-lang.com; Path=/; HttpOnly; SameSite=Strict")
+    );
+
+    assert_eq_ser!(
+        CookieTemplate::new("foo").quoted().with_value("bar"),
+        Ok("foo=\"bar\"")
+    );
+}
+
+#[test]
+fn template_prefix() {
+    const HOST: CookieTemplate = CookieTemplate::host("id");
+    assert_eq_ser!(HOST.with_value("1"), Ok("__Host-id=1; Path=/; Secure"));
+
+    const SECURE: CookieTemplate = CookieTemplate::secure_prefixed("id").partitioned();
+    assert_eq_ser!(
+        SECURE.with_value("1"),
+        Ok("__Secure-id=1; Secure; Partitioned")
+    );
+}
+
+#[test]
+fn template_remove() {
+    let removed = SESSION.remove();
+    assert_eq!(removed.value(), "");
+    assert_eq!(removed.max_age_secs(), Some(0));
+    assert_eq!(removed.path(), Some("/"));
+    assert_eq!(removed.domain(), Some("rust-lang.com"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn template_remove_with() {
+    use cookie_monster::FixedClock;
+
+    let removed = SESSION.remove_with(&FixedClock::from_unix_timestamp(784_111_777));
+    assert_eq_ser!(
+        removed,
+        Ok(
+            "session=; Max-Age=0; Domain=rust-lang.com; Path=/; HttpOnly; SameSite=Strict; Expires=Sat, 06 Nov 1993 08:49:37 GMT"
+        )
+    );
+}
+
+#[test]
+fn try_build() {
+    let cookie = Cookie::build("foo", "bar")
+        .path("/")
+        .domain(".rust-lang.com")
+        .try_build();
+    assert_eq!(cookie.map(|c| c.value().to_string()), Ok("bar".to_string()));
+
+    let error = Cookie::build("", "bar").try_build().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::NameEmpty);
+
+    let error = Cookie::build("foo", "b r").try_build().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidValue(' '));
+    assert_eq!(error.name(), Some("foo"));
+
+    // The encoded value is validated.
+    let cookie = Cookie::build("foo", "b r").try_build_with(&Base64UrlCodec);
+    assert!(cookie.is_ok());
+
+    let error = Cookie::build("foo", "bar")
+        .path("no-slash")
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::NoLeadingSlash);
+    assert_eq!(error.name(), Some("foo"));
+
+    let error = Cookie::build("foo", "bar")
+        .path("")
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::EmptyPathValue);
+
+    let error = Cookie::build("foo", "bar")
+        .domain(".rust-lang\0.com")
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidDomainValue('\0'));
+    assert_eq!(error.phase(), Phase::Serialize(Attribute::Domain));
+    assert_eq!(error.name(), Some("foo"));
+    assert_eq!(error.offset(), Some(10));
+}
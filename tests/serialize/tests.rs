@@ -10,9 +10,15 @@ mod partitioned;
 mod path;
 mod same_site;
 mod secure;
+mod template;
 
 #[cfg(feature = "percent-encode")]
 mod encoded;
 
 #[cfg(feature = "axum")]
 mod axum;
+
+// The helpers shared with the other test binaries.
+#[cfg(feature = "axum")]
+#[path = "../util/mod.rs"]
+mod shared;
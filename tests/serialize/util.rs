@@ -2,7 +2,7 @@
 macro_rules! assert_eq_ser {
     ($string:expr, $cookie:expr) => {
         assert_eq!(
-            $string.serialize().as_deref(),
+            $string.serialize().as_deref().map_err(|e| e.kind()),
             $cookie,
             "serialize went wrong"
         );
@@ -13,7 +13,7 @@ macro_rules! assert_eq_ser {
 macro_rules! assert_eq_ser_enc {
     ($string:expr, $cookie:expr) => {
         assert_eq!(
-            $string.serialize_encoded().as_deref(),
+            $string.serialize_encoded().as_deref().map_err(|e| e.kind()),
             $cookie,
             "serialize went wrong"
         );
@@ -0,0 +1,114 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, Response},
+    routing::get,
+};
+use cookie_monster::{Cookie, CookieJar, FixedClock, TestClient};
+
+mod util;
+
+use util::ready;
+
+fn get_request(client: &mut TestClient<Router>, uri: &str) -> Response<Body> {
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+    ready(client.oneshot(request)).unwrap()
+}
+
+async fn echo(jar: CookieJar) -> String {
+    let mut cookies: Vec<_> = jar
+        .iter()
+        .map(|c| format!("{}={}", c.name(), c.value()))
+        .collect();
+    cookies.sort();
+    cookies.join(",")
+}
+
+fn echoed(client: &mut TestClient<Router>, uri: &str) -> String {
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+    let response = ready(client.oneshot(request)).unwrap();
+    response
+        .headers()
+        .get("x-cookies")
+        .map(|h| h.to_str().unwrap().to_owned())
+        .unwrap_or_default()
+}
+
+fn app() -> Router {
+    async fn login(mut jar: CookieJar) -> CookieJar {
+        jar.add(
+            Cookie::build("session", "hello, world")
+                .path("/")
+                .max_age_secs(3600)
+                .http_only(),
+        );
+        jar.add(Cookie::new("theme", "dark"));
+        jar
+    }
+
+    async fn logout(mut jar: CookieJar) -> CookieJar {
+        jar.remove(Cookie::build("session", "").path("/"));
+        jar
+    }
+
+    async fn cookies(jar: CookieJar) -> ([(&'static str, String); 1], ()) {
+        ([("x-cookies", echo(jar).await)], ())
+    }
+
+    Router::new()
+        .route("/auth/login", get(login))
+        .route("/auth/logout", get(logout))
+        .route("/auth/me", get(cookies))
+        .route("/me", get(cookies))
+}
+
+#[test]
+fn carries_cookies_across_requests() {
+    let mut client = TestClient::new(app());
+    assert_eq!(echoed(&mut client, "/me"), "");
+
+    get_request(&mut client, "/auth/login");
+
+    let session = client.cookie("session").unwrap();
+    assert_eq!(session.value(), "hello, world");
+    assert_eq!(session.max_age_secs(), Some(3600));
+    assert!(session.is_http_only());
+
+    // Without a Path attribute, the cookie is only sent to `/auth` paths.
+    assert_eq!(client.cookie("theme").unwrap().path(), Some("/auth"));
+    assert_eq!(echoed(&mut client, "/me"), "session=hello, world");
+    assert_eq!(
+        echoed(&mut client, "/auth/me"),
+        "session=hello, world,theme=dark"
+    );
+
+    get_request(&mut client, "/auth/logout");
+    assert_eq!(client.cookie("session"), None);
+    assert_eq!(echoed(&mut client, "/auth/me"), "theme=dark");
+}
+
+#[test]
+fn expires_cookies() {
+    let mut client = TestClient::new(app());
+    client.set_clock(FixedClock::from_unix_timestamp(1_000_000));
+    get_request(&mut client, "/auth/login");
+    assert!(client.cookie("session").is_some());
+
+    client.set_clock(FixedClock::from_unix_timestamp(1_003_600));
+    assert_eq!(client.cookie("session"), None);
+    assert_eq!(echoed(&mut client, "/auth/me"), "theme=dark");
+}
+
+#[test]
+fn added_cookies() {
+    let mut client = TestClient::new(app());
+    client.add_cookie(Cookie::new("lang", "en"));
+    client.add_cookie(Cookie::host("id", "abc"));
+
+    let names: Vec<_> = client.cookies().map(Cookie::name).collect();
+    assert_eq!(names, ["lang", "id"]);
+    assert_eq!(echoed(&mut client, "/me"), "id=abc,lang=en");
+
+    client.clear_cookies();
+    assert_eq!(client.cookies().count(), 0);
+}
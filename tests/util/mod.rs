@@ -0,0 +1,30 @@
+// Helpers shared by the test binaries, not every binary uses every helper.
+#![allow(dead_code)]
+
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use axum::{extract::FromRequestParts, http::Request};
+use cookie_monster::CookieJar;
+
+/// Polls the future once and returns its output. The handlers and extractors in the tests never
+/// wait on I/O, so they complete on the first poll.
+pub fn ready<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future should be ready"),
+    }
+}
+
+/// Extracts a `CookieJar` from the request like an axum handler does.
+pub fn extract_jar<B>(request: Request<B>) -> CookieJar {
+    let (mut parts, _) = request.into_parts();
+    let Ok(jar) = ready(CookieJar::from_request_parts(&mut parts, &()));
+    jar
+}